@@ -10,6 +10,9 @@ import (
 	"sync"
 	"time"
 
+	errorsmod "cosmossdk.io/errors"
+	"cosmossdk.io/math"
+	storetypes "cosmossdk.io/store/types"
 	"github.com/InjectiveLabs/test-tube/injective-test-tube/result"
 	"github.com/InjectiveLabs/test-tube/injective-test-tube/testenv"
 	abci "github.com/cometbft/cometbft/abci/types"
@@ -20,7 +23,10 @@ import (
 	"github.com/cosmos/gogoproto/proto"
 	"github.com/pkg/errors"
 
+	wasmkeeper "github.com/CosmWasm/wasmd/x/wasm/keeper"
 	wasmtypes "github.com/CosmWasm/wasmd/x/wasm/types"
+
+	oracletypes "github.com/InjectiveLabs/injective-core/injective-chain/modules/oracle/types"
 )
 
 var (
@@ -121,6 +127,11 @@ func IncreaseTime(envId uint64, seconds uint64) {
 	internalFinalizeBlock(envId, "", seconds)
 }
 
+//export NextBlock
+func NextBlock(envId uint64, seconds uint64) *C.char {
+	return internalFinalizeBlock(envId, "", seconds)
+}
+
 //export FinalizeBlock
 func FinalizeBlock(envId uint64, base64ReqDeliverTx string) *C.char {
 	return internalFinalizeBlock(envId, base64ReqDeliverTx, 1)
@@ -247,7 +258,7 @@ func Simulate(envId uint64, base64TxBytes string) *C.char { // => base64GasInfo
 
 	gasInfo, _, err := env.App.Simulate(txBytes)
 	if err != nil {
-		return encodeErrToResultBytes(result.ExecuteError, err)
+		return encodeTxErrToResultBytes(err, gasInfo)
 	}
 
 	bz, err := proto.Marshal(&gasInfo)
@@ -326,6 +337,135 @@ func GetParamSet(envId uint64, subspaceName, typeUrl string) *C.char {
 	return encodeBytesResultBytes(bz)
 }
 
+//export SetOraclePrice
+func SetOraclePrice(envId uint64, oracleType int32, base, quote, price string) *C.char {
+	env := loadEnv(envId)
+
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	priceDec, err := math.LegacyNewDecFromStr(price)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	err = env.SetOraclePrice(oracletypes.OracleType(oracleType), base, quote, priceDec)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export SetContractStorage
+func SetContractStorage(envId uint64, bech32ContractAddress, base64Key, base64Value string) *C.char {
+	env := loadEnv(envId)
+
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	contractAddress, err := sdk.AccAddressFromBech32(bech32ContractAddress)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	key, err := base64.StdEncoding.DecodeString(base64Key)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	value, err := base64.StdEncoding.DecodeString(base64Value)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	err = env.SetContractStorage(contractAddress, key, value)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export DeleteContractStorage
+func DeleteContractStorage(envId uint64, bech32ContractAddress, base64Key string) *C.char {
+	env := loadEnv(envId)
+
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	contractAddress, err := sdk.AccAddressFromBech32(bech32ContractAddress)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	key, err := base64.StdEncoding.DecodeString(base64Key)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	err = env.DeleteContractStorage(contractAddress, key)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envRegister.Store(envId, env)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+type sudoResult struct {
+	Data    []byte `json:"data"`
+	GasUsed uint64 `json:"gas_used"`
+}
+
+//export Sudo
+func Sudo(envId uint64, bech32ContractAddress, msgJson string, simulate bool) *C.char {
+	env := loadEnv(envId)
+
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	contractAddress, err := sdk.AccAddressFromBech32(bech32ContractAddress)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	ctx, write := env.Ctx.CacheContext()
+	ctx = ctx.WithGasMeter(storetypes.NewInfiniteGasMeter())
+
+	data, err := wasmkeeper.NewDefaultPermissionKeeper(env.App.WasmKeeper).Sudo(ctx, contractAddress, []byte(msgJson))
+	if err != nil {
+		return encodeTxErrToResultBytes(err, sdk.GasInfo{
+			GasWanted: ctx.GasMeter().Limit(),
+			GasUsed:   ctx.GasMeter().GasConsumed(),
+		})
+	}
+
+	if !simulate {
+		write()
+		envRegister.Store(envId, env)
+	}
+
+	bz, err := json.Marshal(sudoResult{Data: data, GasUsed: ctx.GasMeter().GasConsumed()})
+	if err != nil {
+		panic(err)
+	}
+
+	return encodeBytesResultBytes(bz)
+}
+
 //export GetValidatorAddress
 func GetValidatorAddress(envId uint64, n int32) *C.char {
 	env := loadEnv(envId)
@@ -356,6 +496,33 @@ func encodeErrToResultBytes(code byte, err error) *C.char {
 	return C.CString(result.EncodeResultFromError(code, err))
 }
 
+type txError struct {
+	Log       string `json:"log"`
+	Codespace string `json:"codespace"`
+	Code      uint32 `json:"code"`
+	GasWanted uint64 `json:"gas_wanted"`
+	GasUsed   uint64 `json:"gas_used"`
+}
+
+// encodeTxErrToResultBytes encodes the error of a failed tx along with its ABCI codespace and code,
+// the same way they would be reported in the tx result.
+func encodeTxErrToResultBytes(err error, gasInfo sdk.GasInfo) *C.char {
+	codespace, code, log := errorsmod.ABCIInfo(err, false)
+
+	bz, err := json.Marshal(txError{
+		Log:       log,
+		Codespace: codespace,
+		Code:      code,
+		GasWanted: gasInfo.GasWanted,
+		GasUsed:   gasInfo.GasUsed,
+	})
+	if err != nil {
+		panic(err)
+	}
+
+	return C.CString(result.EncodeResultFromTxError(bz))
+}
+
 func encodeBytesResultBytes(bytes []byte) *C.char {
 	return C.CString(result.EncodeResultFromOk(bytes))
 }
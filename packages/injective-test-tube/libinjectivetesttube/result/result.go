@@ -9,6 +9,7 @@ var (
 	Ok           byte = 0
 	QueryError   byte = 1
 	ExecuteError byte = 2
+	TxError      byte = 3
 )
 
 func markError(code byte, data []byte) []byte {
@@ -24,6 +25,12 @@ func EncodeResultFromError(code byte, err error) string {
 	return base64.StdEncoding.EncodeToString(marked)
 }
 
+// EncodeResultFromTxError encodes the json encoded ABCI error info of a failed tx.
+func EncodeResultFromTxError(data []byte) string {
+	marked := markError(TxError, data)
+	return base64.StdEncoding.EncodeToString(marked)
+}
+
 func EncodeResultFromOk(data []byte) string {
 	marked := markOk(data)
 	return base64.StdEncoding.EncodeToString(marked)
@@ -0,0 +1,133 @@
+package testenv
+
+import (
+	"fmt"
+
+	"cosmossdk.io/math"
+	"github.com/ethereum/go-ethereum/common"
+
+	oracletypes "github.com/InjectiveLabs/injective-core/injective-chain/modules/oracle/types"
+)
+
+// band rates are relayed with 9 decimals, coinbase prices with 6
+var (
+	bandRateMultiplier      = math.LegacyNewDec(1_000_000_000)
+	coinbasePriceMultiplier = math.LegacyNewDec(1_000_000)
+)
+
+// SetOraclePrice writes the price of base/quote for the given oracle type directly into the
+// oracle keeper, bypassing relayer privileges and price attestations.
+//
+// Symbol based oracles price a pair as base price / quote price, so the quote symbol is set to 1
+// unless it already has a price, and the base symbol to price times the quote price. For the
+// provider oracle, base is the symbol and quote the provider.
+func (env *TestEnv) SetOraclePrice(oracleType oracletypes.OracleType, base, quote string, price math.LegacyDec) error {
+	ctx := env.Ctx
+	keeper := env.App.OracleKeeper
+	blockTime := ctx.BlockTime().Unix()
+
+	setSymbolPrice := func(symbol string, price math.LegacyDec) error {
+		priceState := *oracletypes.NewPriceState(price, blockTime)
+
+		switch oracleType {
+		case oracletypes.OracleType_Band, oracletypes.OracleType_BandIBC:
+			bandPriceState := &oracletypes.BandPriceState{
+				Symbol:      symbol,
+				Rate:        price.Mul(bandRateMultiplier).TruncateInt(),
+				ResolveTime: uint64(blockTime),
+				PriceState:  priceState,
+			}
+			if oracleType == oracletypes.OracleType_Band {
+				return keeper.SetBandPriceState(ctx, symbol, bandPriceState)
+			}
+			return keeper.SetBandIBCPriceState(ctx, symbol, bandPriceState)
+		case oracletypes.OracleType_Coinbase:
+			value := price.Mul(coinbasePriceMultiplier).TruncateInt()
+			if !value.IsUint64() {
+				return fmt.Errorf("coinbase price %s of %s is out of range", price, symbol)
+			}
+			return keeper.SetCoinbasePriceState(ctx, &oracletypes.CoinbasePriceState{
+				Kind:       "prices",
+				Timestamp:  uint64(blockTime),
+				Key:        symbol,
+				Value:      value.Uint64(),
+				PriceState: priceState,
+			})
+		case oracletypes.OracleType_Pyth:
+			keeper.SetPythPriceState(ctx, &oracletypes.PythPriceState{
+				PriceId:     symbol,
+				EmaPrice:    price,
+				EmaConf:     math.LegacyZeroDec(),
+				Conf:        math.LegacyZeroDec(),
+				PublishTime: uint64(blockTime),
+				PriceState:  priceState,
+			})
+			return nil
+		case oracletypes.OracleType_Stork:
+			keeper.SetStorkPriceState(ctx, &oracletypes.StorkPriceState{
+				Timestamp:  uint64(blockTime),
+				Symbol:     symbol,
+				Value:      price,
+				PriceState: priceState,
+			})
+			return nil
+		default:
+			return fmt.Errorf("setting prices is not supported for oracle type %s", oracleType)
+		}
+	}
+
+	getSymbolPrice := func(symbol string) *math.LegacyDec {
+		var priceState *oracletypes.PriceState
+
+		switch oracleType {
+		case oracletypes.OracleType_Band:
+			if state := keeper.GetBandPriceState(ctx, symbol); state != nil {
+				priceState = &state.PriceState
+			}
+		case oracletypes.OracleType_BandIBC:
+			if state := keeper.GetBandIBCPriceState(ctx, symbol); state != nil {
+				priceState = &state.PriceState
+			}
+		case oracletypes.OracleType_Coinbase:
+			if state := keeper.GetCoinbasePriceState(ctx, symbol); state != nil {
+				priceState = &state.PriceState
+			}
+		case oracletypes.OracleType_Pyth:
+			if state := keeper.GetPythPriceState(ctx, common.HexToHash(symbol)); state != nil {
+				priceState = &state.PriceState
+			}
+		case oracletypes.OracleType_Stork:
+			if state := keeper.GetStorkPriceState(ctx, symbol); state != nil {
+				priceState = &state.PriceState
+			}
+		}
+
+		if priceState == nil || !priceState.Price.IsPositive() {
+			return nil
+		}
+		return &priceState.Price
+	}
+
+	switch oracleType {
+	case oracletypes.OracleType_PriceFeed:
+		keeper.SetPriceFeedInfo(ctx, &oracletypes.PriceFeedInfo{Base: base, Quote: quote})
+		keeper.SetPriceFeedPriceState(ctx, base, quote, oracletypes.NewPriceState(price, blockTime))
+		return nil
+	case oracletypes.OracleType_Provider:
+		keeper.SetProviderPriceState(ctx, quote, &oracletypes.ProviderPriceState{
+			Symbol: base,
+			State:  oracletypes.NewPriceState(price, blockTime),
+		})
+		return nil
+	default:
+		quotePrice := getSymbolPrice(quote)
+		if quotePrice == nil {
+			one := math.LegacyOneDec()
+			if err := setSymbolPrice(quote, one); err != nil {
+				return err
+			}
+			quotePrice = &one
+		}
+		return setSymbolPrice(base, price.Mul(*quotePrice))
+	}
+}
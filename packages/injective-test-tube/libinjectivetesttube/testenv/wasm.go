@@ -0,0 +1,42 @@
+package testenv
+
+import (
+	"fmt"
+
+	"cosmossdk.io/store/prefix"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	wasmtypes "github.com/CosmWasm/wasmd/x/wasm/types"
+)
+
+// SetContractStorage writes value under key in the raw storage of a contract, the same
+// store cosmwasm storage helpers such as cw-storage-plus read from.
+func (env *TestEnv) SetContractStorage(contractAddress sdk.AccAddress, key, value []byte) error {
+	store, err := env.contractStore(contractAddress)
+	if err != nil {
+		return err
+	}
+
+	store.Set(key, value)
+	return nil
+}
+
+// DeleteContractStorage removes key from the raw storage of a contract.
+func (env *TestEnv) DeleteContractStorage(contractAddress sdk.AccAddress, key []byte) error {
+	store, err := env.contractStore(contractAddress)
+	if err != nil {
+		return err
+	}
+
+	store.Delete(key)
+	return nil
+}
+
+func (env *TestEnv) contractStore(contractAddress sdk.AccAddress) (prefix.Store, error) {
+	if !env.App.WasmKeeper.HasContractInfo(env.Ctx, contractAddress) {
+		return prefix.Store{}, fmt.Errorf("contract %s not found", contractAddress)
+	}
+
+	storeKey := env.App.GetKey(wasmtypes.StoreKey)
+	return prefix.NewStore(env.Ctx.KVStore(storeKey), wasmtypes.GetContractStorePrefix(contractAddress)), nil
+}
@@ -2,6 +2,7 @@
 
 mod module;
 mod runner;
+mod utils;
 
 pub use cosmrs;
 pub use injective_cosmwasm;
@@ -10,7 +11,10 @@ pub use injective_std;
 pub use module::*;
 pub use runner::app::InjectiveTestApp;
 pub use test_tube_inj::account::{Account, FeeSetting, NonSigningAccount, SigningAccount};
-pub use test_tube_inj::runner::error::{DecodeError, EncodeError, RunnerError};
-pub use test_tube_inj::runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
+pub use test_tube_inj::runner::error::{ContractError, DecodeError, EncodeError, RunnerError};
+pub use test_tube_inj::runner::result::{
+    ExecuteMultipleResponse, ExecuteResponse, RunnerExecuteResult, RunnerResult, SudoResponse,
+};
 pub use test_tube_inj::runner::Runner;
-pub use test_tube_inj::{fn_execute, fn_query};
+pub use test_tube_inj::{assert_contract_err, fn_execute, fn_query};
+pub use utils::{default_subaccount_id, subaccount_id, MarketDecimals};
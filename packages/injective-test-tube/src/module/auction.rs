@@ -1,13 +1,27 @@
+use cosmwasm_std::Coin;
+use injective_std::types::cosmos::auth::v1beta1::{
+    ModuleAccount, QueryModuleAccountByNameRequest, QueryModuleAccountByNameResponse,
+};
+use injective_std::types::cosmos::bank::v1beta1::{MsgSend, MsgSendResponse};
 use injective_std::types::injective::auction::v1beta1::{
-    QueryAuctionParamsRequest, QueryAuctionParamsResponse, QueryCurrentAuctionBasketRequest,
-    QueryCurrentAuctionBasketResponse, QueryLastAuctionResultRequest,
-    QueryLastAuctionResultResponse, QueryModuleStateRequest, QueryModuleStateResponse,
+    MsgBid, MsgBidResponse, QueryAuctionParamsRequest, QueryAuctionParamsResponse,
+    QueryCurrentAuctionBasketRequest, QueryCurrentAuctionBasketResponse,
+    QueryLastAuctionResultRequest, QueryLastAuctionResultResponse, QueryModuleStateRequest,
+    QueryModuleStateResponse,
 };
-use test_tube_inj::fn_query;
+use prost::Message;
+use test_tube_inj::account::{Account, SigningAccount};
+use test_tube_inj::runner::error::{DecodeError, RunnerError};
+use test_tube_inj::runner::result::{RunnerExecuteResult, RunnerResult};
+use test_tube_inj::{fn_execute, fn_query};
 
 use test_tube_inj::module::Module;
 use test_tube_inj::runner::Runner;
 
+use crate::InjectiveTestApp;
+
+const AUCTION_MODULE_NAME: &str = "auction";
+
 pub struct Auction<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -22,6 +36,65 @@ impl<'a, R> Auction<'a, R>
 where
     R: Runner<'a>,
 {
+    fn_execute! {
+        pub bid: MsgBid => MsgBidResponse
+    }
+
+    /// Add `amount` to the current auction basket, as the exchange module does with
+    /// the auction share of the trading fees.
+    pub fn seed_auction_basket(
+        &self,
+        amount: &[Coin],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgSendResponse> {
+        let to_address = self.query_module_address()?;
+
+        // invalid coins if denom are unsorted
+        let mut amount = amount.to_vec();
+        amount.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        self.runner.execute(
+            MsgSend {
+                from_address: signer.address(),
+                to_address,
+                amount: amount
+                    .iter()
+                    .map(|c| injective_std::types::cosmos::base::v1beta1::Coin {
+                        denom: c.denom.clone(),
+                        amount: c.amount.to_string(),
+                    })
+                    .collect(),
+            },
+            MsgSend::TYPE_URL,
+            signer,
+        )
+    }
+
+    /// Address of the auction module account, which holds the auction basket, as
+    /// derived by the auth module from the module name.
+    pub fn query_module_address(&self) -> RunnerResult<String> {
+        let account = self
+            .runner
+            .query::<QueryModuleAccountByNameRequest, QueryModuleAccountByNameResponse>(
+                "/cosmos.auth.v1beta1.Query/ModuleAccountByName",
+                &QueryModuleAccountByNameRequest {
+                    name: AUCTION_MODULE_NAME.to_string(),
+                },
+            )?
+            .account
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("{} module account not found", AUCTION_MODULE_NAME),
+            })?;
+
+        ModuleAccount::decode(account.value.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)?
+            .base_account
+            .map(|base_account| base_account.address)
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("{} module account has no address", AUCTION_MODULE_NAME),
+            })
+    }
+
     fn_query! {
         pub query_auction_params ["/injective.auction.v1beta1.Query/AuctionParams"]: QueryAuctionParamsRequest => QueryAuctionParamsResponse
     }
@@ -39,16 +112,50 @@ where
     }
 }
 
+impl<'a> Auction<'a, InjectiveTestApp> {
+    /// Advance the block time to the end of the current auction round, so that
+    /// the round gets settled and the next one starts.
+    pub fn settle_auction_round(&self) -> RunnerResult<()> {
+        let state = self
+            .query_module_state(&QueryModuleStateRequest {})?
+            .state
+            .unwrap_or_default();
+
+        // before the first round is started the ending timestamp is unset,
+        // in which case a full auction period is the upper bound
+        let ending_timestamp = if state.auction_ending_timestamp > 0 {
+            state.auction_ending_timestamp
+        } else {
+            let auction_period = self
+                .query_auction_params(&QueryAuctionParamsRequest {})?
+                .params
+                .unwrap_or_default()
+                .auction_period;
+
+            self.runner.get_block_time_seconds() + auction_period
+        };
+
+        let remaining = (ending_timestamp - self.runner.get_block_time_seconds()).max(0);
+        self.runner.increase_time(remaining as u64 + 1);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use cosmwasm_std::Coin;
     use injective_std::types::{
+        cosmos::bank::v1beta1::QueryBalanceRequest,
         cosmos::base::v1beta1::Coin as BaseCoin,
         injective::auction::v1beta1::{
-            LastAuctionResult, Params, QueryAuctionParamsRequest, QueryLastAuctionResultRequest,
+            LastAuctionResult, MsgBid, Params, QueryAuctionParamsRequest,
+            QueryCurrentAuctionBasketRequest, QueryLastAuctionResultRequest,
+            QueryModuleStateRequest,
         },
     };
 
-    use crate::{Auction, InjectiveTestApp};
+    use crate::{Account, Auction, Bank, InjectiveTestApp};
     use test_tube_inj::Module;
 
     #[test]
@@ -86,4 +193,89 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn auction_bid_and_settle() {
+        let app = InjectiveTestApp::new();
+
+        let auction = Auction::new(&app);
+        let bank = Bank::new(&app);
+
+        let seeder = app
+            .init_account(&[
+                Coin::new(100_000_000_000_000_000_000u128, "inj"),
+                Coin::new(1_000_000u128, "usdt"),
+            ])
+            .unwrap();
+        let bidder = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+
+        // sha256("auction")[..20]
+        assert_eq!(
+            auction.query_module_address().unwrap(),
+            "inj1j4yzhgjm00ch3h0p9kel7g8sp6g045qf32pzlj"
+        );
+
+        auction
+            .seed_auction_basket(&[Coin::new(1_000_000u128, "usdt")], &seeder)
+            .unwrap();
+
+        let basket = auction
+            .query_current_auction_basket(&QueryCurrentAuctionBasketRequest {})
+            .unwrap();
+        assert!(basket.amount.contains(&BaseCoin {
+            denom: "usdt".to_string(),
+            amount: "1000000".to_string(),
+        }));
+
+        let round = auction
+            .query_module_state(&QueryModuleStateRequest {})
+            .unwrap()
+            .state
+            .unwrap()
+            .auction_round;
+
+        auction
+            .bid(
+                MsgBid {
+                    sender: bidder.address(),
+                    bid_amount: Some(BaseCoin {
+                        denom: "inj".to_string(),
+                        amount: "1000000000000000000".to_string(),
+                    }),
+                    round,
+                },
+                &bidder,
+            )
+            .unwrap();
+
+        auction.settle_auction_round().unwrap();
+
+        let result = auction
+            .query_last_auction_result(&QueryLastAuctionResultRequest {})
+            .unwrap()
+            .last_auction_result
+            .unwrap();
+        assert_eq!(result.winner, bidder.address());
+        assert_eq!(result.round, round);
+
+        let balance = bank
+            .query_balance(&QueryBalanceRequest {
+                address: bidder.address(),
+                denom: "usdt".to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000000");
+
+        let next_round = auction
+            .query_module_state(&QueryModuleStateRequest {})
+            .unwrap()
+            .state
+            .unwrap()
+            .auction_round;
+        assert_eq!(next_round, round + 1);
+    }
 }
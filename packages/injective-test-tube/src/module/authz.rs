@@ -1,10 +1,18 @@
+use cosmwasm_std::Coin;
+use injective_std::shim::Any;
 use injective_std::types::cosmos::authz::v1beta1::{
-    MsgExec, MsgExecResponse, MsgGrant, MsgGrantResponse, QueryGranteeGrantsRequest,
-    QueryGranteeGrantsResponse, QueryGranterGrantsRequest, QueryGranterGrantsResponse,
-    QueryGrantsRequest, QueryGrantsResponse,
+    GenericAuthorization, MsgExec, MsgExecResponse, MsgGrant, MsgGrantResponse, MsgRevoke,
+    MsgRevokeResponse, QueryGranteeGrantsRequest, QueryGranteeGrantsResponse,
+    QueryGranterGrantsRequest, QueryGranterGrantsResponse, QueryGrantsRequest, QueryGrantsResponse,
 };
+use injective_std::types::cosmos::bank::v1beta1::SendAuthorization;
+use injective_std::types::cosmos::staking::v1beta1::StakeAuthorization;
+use injective_std::types::injective::exchange::v1beta1;
+use prost::Message;
+use test_tube_inj::runner::result::RunnerExecuteResult;
 use test_tube_inj::{fn_execute, fn_query};
 
+use test_tube_inj::account::{Account, SigningAccount};
 use test_tube_inj::module::Module;
 use test_tube_inj::runner::Runner;
 
@@ -30,6 +38,29 @@ where
         pub grant: MsgGrant["/cosmos.authz.v1beta1.MsgGrant"] => MsgGrantResponse
     }
 
+    fn_execute! {
+        pub revoke: MsgRevoke["/cosmos.authz.v1beta1.MsgRevoke"] => MsgRevokeResponse
+    }
+
+    /// Wrap `msgs` in a `MsgExec` signed by `grantee`, so that they are executed
+    /// on behalf of their respective granters.
+    ///
+    /// Messages of different types can be mixed, build them with their `to_any()`
+    /// method so that their type urls come from their types.
+    pub fn exec_as(
+        &self,
+        grantee: &SigningAccount,
+        msgs: &[Any],
+    ) -> RunnerExecuteResult<MsgExecResponse> {
+        self.exec(
+            MsgExec {
+                grantee: grantee.address(),
+                msgs: msgs.to_vec(),
+            },
+            grantee,
+        )
+    }
+
     fn_query! {
         pub query_grantee_grants ["/cosmos.authz.v1beta1.Query/GranteeGrants"]: QueryGranteeGrantsRequest => QueryGranteeGrantsResponse
     }
@@ -43,21 +74,146 @@ where
     }
 }
 
+fn authorization_to_any<A: Message>(authorization: &A, type_url: &str) -> Any {
+    Any {
+        type_url: type_url.to_string(),
+        value: authorization.encode_to_vec(),
+    }
+}
+
+/// Build a `GenericAuthorization` allowing the grantee to execute any message of `msg_type_url`.
+pub fn generic_authorization(msg_type_url: &str) -> Any {
+    authorization_to_any(
+        &GenericAuthorization {
+            msg: msg_type_url.to_string(),
+        },
+        GenericAuthorization::TYPE_URL,
+    )
+}
+
+/// Build a `SendAuthorization` limited to `spend_limit`, optionally restricted to the
+/// receivers in `allow_list`.
+pub fn send_authorization(spend_limit: &[Coin], allow_list: &[String]) -> Any {
+    // invalid coins if denom are unsorted
+    let mut coins = spend_limit.to_vec();
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    authorization_to_any(
+        &SendAuthorization {
+            spend_limit: coins
+                .iter()
+                .map(|c| injective_std::types::cosmos::base::v1beta1::Coin {
+                    denom: c.denom.clone(),
+                    amount: c.amount.to_string(),
+                })
+                .collect(),
+            allow_list: allow_list.to_vec(),
+        },
+        SendAuthorization::TYPE_URL,
+    )
+}
+
+/// Wrap a `StakeAuthorization` (delegate, undelegate or redelegate) into a grantable `Any`.
+pub fn stake_authorization(authorization: StakeAuthorization) -> Any {
+    authorization_to_any(&authorization, StakeAuthorization::TYPE_URL)
+}
+
+macro_rules! exchange_market_authz {
+    ($(#[$meta:meta])* $name:ident: $authz:ident) => {
+        $(#[$meta])*
+        pub fn $name(subaccount_id: &str, market_ids: &[String]) -> Any {
+            authorization_to_any(
+                &v1beta1::$authz {
+                    subaccount_id: subaccount_id.to_string(),
+                    market_ids: market_ids.to_vec(),
+                },
+                v1beta1::$authz::TYPE_URL,
+            )
+        }
+    };
+}
+
+exchange_market_authz! {
+    /// Build a `CreateSpotLimitOrderAuthz` for `subaccount_id` on `market_ids`.
+    create_spot_limit_order_authz: CreateSpotLimitOrderAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `CreateSpotMarketOrderAuthz` for `subaccount_id` on `market_ids`.
+    create_spot_market_order_authz: CreateSpotMarketOrderAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `BatchCreateSpotLimitOrdersAuthz` for `subaccount_id` on `market_ids`.
+    batch_create_spot_limit_orders_authz: BatchCreateSpotLimitOrdersAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `CancelSpotOrderAuthz` for `subaccount_id` on `market_ids`.
+    cancel_spot_order_authz: CancelSpotOrderAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `BatchCancelSpotOrdersAuthz` for `subaccount_id` on `market_ids`.
+    batch_cancel_spot_orders_authz: BatchCancelSpotOrdersAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `CreateDerivativeLimitOrderAuthz` for `subaccount_id` on `market_ids`.
+    create_derivative_limit_order_authz: CreateDerivativeLimitOrderAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `CreateDerivativeMarketOrderAuthz` for `subaccount_id` on `market_ids`.
+    create_derivative_market_order_authz: CreateDerivativeMarketOrderAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `BatchCreateDerivativeLimitOrdersAuthz` for `subaccount_id` on `market_ids`.
+    batch_create_derivative_limit_orders_authz: BatchCreateDerivativeLimitOrdersAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `CancelDerivativeOrderAuthz` for `subaccount_id` on `market_ids`.
+    cancel_derivative_order_authz: CancelDerivativeOrderAuthz
+}
+
+exchange_market_authz! {
+    /// Build a `BatchCancelDerivativeOrdersAuthz` for `subaccount_id` on `market_ids`.
+    batch_cancel_derivative_orders_authz: BatchCancelDerivativeOrdersAuthz
+}
+
+/// Build a `BatchUpdateOrdersAuthz` for `subaccount_id` on the given spot and derivative markets.
+pub fn batch_update_orders_authz(
+    subaccount_id: &str,
+    spot_markets: &[String],
+    derivative_markets: &[String],
+) -> Any {
+    authorization_to_any(
+        &v1beta1::BatchUpdateOrdersAuthz {
+            subaccount_id: subaccount_id.to_string(),
+            spot_markets: spot_markets.to_vec(),
+            derivative_markets: derivative_markets.to_vec(),
+        },
+        v1beta1::BatchUpdateOrdersAuthz::TYPE_URL,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::Coin;
-    use injective_std::shim::Any;
     use injective_std::types::{
         cosmos::authz::v1beta1::{
-            GenericAuthorization, Grant, GrantAuthorization, MsgExec, MsgGrant,
-            QueryGranteeGrantsRequest, QueryGranterGrantsRequest,
+            Grant, GrantAuthorization, MsgGrant, MsgRevoke, QueryGranteeGrantsRequest,
+            QueryGranterGrantsRequest,
         },
-        cosmos::bank::v1beta1::{MsgSend, QueryBalanceRequest, SendAuthorization},
+        cosmos::bank::v1beta1::{MsgSend, QueryBalanceRequest},
         cosmos::base::v1beta1::Coin as BaseCoin,
     };
-    use prost::Message;
 
-    use crate::{Account, Authz, Bank, InjectiveTestApp};
+    use crate::{
+        generic_authorization, send_authorization, Account, Authz, Bank, InjectiveTestApp,
+    };
     use test_tube_inj::Module;
 
     #[test]
@@ -66,7 +222,7 @@ mod tests {
         let signer = app
             .init_account(&[
                 Coin::new(100_000_000_000_000_000_000u128, "inj"),
-                Coin::new(10u128, "usdc"),
+                Coin::new(20u128, "usdc"),
             ])
             .unwrap();
         let receiver = app
@@ -83,18 +239,7 @@ mod tests {
             .unwrap();
         assert_eq!(response.grants, vec![]);
 
-        let mut buf = vec![];
-        SendAuthorization::encode(
-            &SendAuthorization {
-                spend_limit: vec![BaseCoin {
-                    amount: 10u128.to_string(),
-                    denom: "usdc".to_string(),
-                }],
-                allow_list: vec![],
-            },
-            &mut buf,
-        )
-        .unwrap();
+        let send_authz = send_authorization(&[Coin::new(10u128, "usdc")], &[]);
 
         authz
             .grant(
@@ -102,10 +247,7 @@ mod tests {
                     granter: signer.address(),
                     grantee: receiver.address(),
                     grant: Some(Grant {
-                        authorization: Some(Any {
-                            type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
-                            value: buf.clone(),
-                        }),
+                        authorization: Some(send_authz.clone()),
                         expiration: None,
                     }),
                 },
@@ -124,22 +266,13 @@ mod tests {
             vec![GrantAuthorization {
                 granter: signer.address(),
                 grantee: receiver.address(),
-                authorization: Some(Any {
-                    type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
-                    value: buf.clone(),
-                }),
+                authorization: Some(send_authz.clone()),
                 expiration: None,
             }]
         );
 
-        let mut buf_2 = vec![];
-        GenericAuthorization::encode(
-            &GenericAuthorization {
-                msg: "/injective.exchange.v1beta1.MsgCreateSpotLimitOrder".to_string(),
-            },
-            &mut buf_2,
-        )
-        .unwrap();
+        let generic_authz =
+            generic_authorization("/injective.exchange.v1beta1.MsgCreateSpotLimitOrder");
 
         authz
             .grant(
@@ -147,10 +280,7 @@ mod tests {
                     granter: signer.address(),
                     grantee: receiver.address(),
                     grant: Some(Grant {
-                        authorization: Some(Any {
-                            type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
-                            value: buf_2.clone(),
-                        }),
+                        authorization: Some(generic_authz.clone()),
                         expiration: None,
                     }),
                 },
@@ -170,19 +300,13 @@ mod tests {
                 GrantAuthorization {
                     granter: signer.address(),
                     grantee: receiver.address(),
-                    authorization: Some(Any {
-                        type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
-                        value: buf.clone(),
-                    }),
+                    authorization: Some(send_authz.clone()),
                     expiration: None,
                 },
                 GrantAuthorization {
                     granter: signer.address(),
                     grantee: receiver.address(),
-                    authorization: Some(Any {
-                        type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
-                        value: buf_2.clone(),
-                    }),
+                    authorization: Some(generic_authz.clone()),
                     expiration: None,
                 }
             ]
@@ -200,19 +324,13 @@ mod tests {
                 GrantAuthorization {
                     granter: signer.address(),
                     grantee: receiver.address(),
-                    authorization: Some(Any {
-                        type_url: "/cosmos.bank.v1beta1.SendAuthorization".to_string(),
-                        value: buf,
-                    }),
+                    authorization: Some(send_authz.clone()),
                     expiration: None,
                 },
                 GrantAuthorization {
                     granter: signer.address(),
                     grantee: receiver.address(),
-                    authorization: Some(Any {
-                        type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
-                        value: buf_2,
-                    }),
+                    authorization: Some(generic_authz),
                     expiration: None,
                 }
             ]
@@ -232,30 +350,18 @@ mod tests {
             }
         );
 
-        let mut buf_3 = vec![];
-        MsgSend::encode(
-            &MsgSend {
-                from_address: signer.address(),
-                to_address: receiver.address(),
-                amount: vec![BaseCoin {
-                    amount: 10u128.to_string(),
-                    denom: "usdc".to_string(),
-                }],
-            },
-            &mut buf_3,
-        )
-        .unwrap();
-
         authz
-            .exec(
-                MsgExec {
-                    grantee: receiver.address(),
-                    msgs: vec![Any {
-                        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
-                        value: buf_3.clone(),
-                    }],
-                },
+            .exec_as(
                 &receiver,
+                &[MsgSend {
+                    from_address: signer.address(),
+                    to_address: receiver.address(),
+                    amount: vec![BaseCoin {
+                        amount: 10u128.to_string(),
+                        denom: "usdc".to_string(),
+                    }],
+                }
+                .to_any()],
             )
             .unwrap();
 
@@ -272,5 +378,41 @@ mod tests {
                 denom: "usdc".to_string(),
             }
         );
+
+        authz
+            .revoke(
+                MsgRevoke {
+                    granter: signer.address(),
+                    grantee: receiver.address(),
+                    msg_type_url: "/injective.exchange.v1beta1.MsgCreateSpotLimitOrder".to_string(),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        // the send authorization has been used up, and the generic one was revoked
+        let response = authz
+            .query_grantee_grants(&QueryGranteeGrantsRequest {
+                grantee: receiver.address(),
+                pagination: None,
+            })
+            .unwrap();
+        assert_eq!(response.grants, vec![]);
+
+        // executing on behalf of the granter without a grant must fail
+        authz
+            .exec_as(
+                &receiver,
+                &[MsgSend {
+                    from_address: signer.address(),
+                    to_address: receiver.address(),
+                    amount: vec![BaseCoin {
+                        amount: 10u128.to_string(),
+                        denom: "usdc".to_string(),
+                    }],
+                }
+                .to_any()],
+            )
+            .unwrap_err();
     }
 }
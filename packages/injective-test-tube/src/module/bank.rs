@@ -1,6 +1,12 @@
 use injective_std::types::cosmos::bank::v1beta1::{
-    MsgSend, MsgSendResponse, QueryAllBalancesRequest, QueryAllBalancesResponse,
-    QueryBalanceRequest, QueryBalanceResponse, QueryTotalSupplyRequest, QueryTotalSupplyResponse,
+    MsgMultiSend, MsgMultiSendResponse, MsgSend, MsgSendResponse, MsgSetSendEnabled,
+    MsgSetSendEnabledResponse, QueryAllBalancesRequest, QueryAllBalancesResponse,
+    QueryBalanceRequest, QueryBalanceResponse, QueryDenomMetadataRequest,
+    QueryDenomMetadataResponse, QueryDenomOwnersRequest, QueryDenomOwnersResponse,
+    QueryDenomsMetadataRequest, QueryDenomsMetadataResponse, QueryParamsRequest,
+    QueryParamsResponse, QuerySendEnabledRequest, QuerySendEnabledResponse,
+    QuerySpendableBalancesRequest, QuerySpendableBalancesResponse, QuerySupplyOfRequest,
+    QuerySupplyOfResponse, QueryTotalSupplyRequest, QueryTotalSupplyResponse,
 };
 use test_tube_inj::{fn_execute, fn_query};
 
@@ -25,6 +31,16 @@ where
         pub send: MsgSend["/cosmos.bank.v1beta1.MsgSend"] => MsgSendResponse
     }
 
+    fn_execute! {
+        pub multi_send: MsgMultiSend["/cosmos.bank.v1beta1.MsgMultiSend"] => MsgMultiSendResponse
+    }
+
+    // NOTE: the authority must be the gov module account, so this is usually
+    // submitted as part of a governance proposal
+    fn_execute! {
+        pub set_send_enabled: MsgSetSendEnabled["/cosmos.bank.v1beta1.MsgSetSendEnabled"] => MsgSetSendEnabledResponse
+    }
+
     fn_query! {
         pub query_balance ["/cosmos.bank.v1beta1.Query/Balance"]: QueryBalanceRequest => QueryBalanceResponse
     }
@@ -36,12 +52,44 @@ where
     fn_query! {
         pub query_total_supply ["/cosmos.bank.v1beta1.Query/TotalSupply"]: QueryTotalSupplyRequest => QueryTotalSupplyResponse
     }
+
+    fn_query! {
+        pub query_spendable_balances ["/cosmos.bank.v1beta1.Query/SpendableBalances"]: QuerySpendableBalancesRequest => QuerySpendableBalancesResponse
+    }
+
+    fn_query! {
+        pub query_supply_of ["/cosmos.bank.v1beta1.Query/SupplyOf"]: QuerySupplyOfRequest => QuerySupplyOfResponse
+    }
+
+    fn_query! {
+        pub query_denom_metadata ["/cosmos.bank.v1beta1.Query/DenomMetadata"]: QueryDenomMetadataRequest => QueryDenomMetadataResponse
+    }
+
+    fn_query! {
+        pub query_denoms_metadata ["/cosmos.bank.v1beta1.Query/DenomsMetadata"]: QueryDenomsMetadataRequest => QueryDenomsMetadataResponse
+    }
+
+    fn_query! {
+        pub query_denom_owners ["/cosmos.bank.v1beta1.Query/DenomOwners"]: QueryDenomOwnersRequest => QueryDenomOwnersResponse
+    }
+
+    fn_query! {
+        pub query_send_enabled ["/cosmos.bank.v1beta1.Query/SendEnabled"]: QuerySendEnabledRequest => QuerySendEnabledResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.bank.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::Coin;
-    use injective_std::types::cosmos::bank::v1beta1::{MsgSend, QueryBalanceRequest};
+    use injective_std::types::cosmos::bank::v1beta1::{
+        Input, MsgMultiSend, MsgSend, Output, QueryBalanceRequest, QueryDenomMetadataRequest,
+        QueryDenomOwnersRequest, QueryParamsRequest, QuerySpendableBalancesRequest,
+        QuerySupplyOfRequest,
+    };
     use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
 
     use crate::{Account, Bank, InjectiveTestApp};
@@ -51,9 +99,13 @@ mod tests {
     fn bank_integration() {
         let app = InjectiveTestApp::new();
         let signer = app
-            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .init_account(&[
+                Coin::new(100_000_000_000_000_000_000u128, "inj"),
+                Coin::new(1_000u128, "usdt"),
+            ])
             .unwrap();
         let receiver = app.init_account(&[Coin::new(1u128, "inj")]).unwrap();
+        let other_receiver = app.init_account(&[Coin::new(1u128, "inj")]).unwrap();
         let bank = Bank::new(&app);
 
         let response = bank
@@ -82,5 +134,93 @@ mod tests {
             &signer,
         )
         .unwrap();
+
+        bank.multi_send(
+            MsgMultiSend {
+                inputs: vec![Input {
+                    address: signer.address(),
+                    coins: vec![BaseCoin {
+                        amount: 300u128.to_string(),
+                        denom: "usdt".to_string(),
+                    }],
+                }],
+                outputs: vec![
+                    Output {
+                        address: receiver.address(),
+                        coins: vec![BaseCoin {
+                            amount: 100u128.to_string(),
+                            denom: "usdt".to_string(),
+                        }],
+                    },
+                    Output {
+                        address: other_receiver.address(),
+                        coins: vec![BaseCoin {
+                            amount: 200u128.to_string(),
+                            denom: "usdt".to_string(),
+                        }],
+                    },
+                ],
+            },
+            &signer,
+        )
+        .unwrap();
+
+        let spendable = bank
+            .query_spendable_balances(&QuerySpendableBalancesRequest {
+                address: other_receiver.address(),
+                pagination: None,
+            })
+            .unwrap()
+            .balances;
+        assert!(spendable.contains(&BaseCoin {
+            amount: 200u128.to_string(),
+            denom: "usdt".to_string(),
+        }));
+
+        let supply = bank
+            .query_supply_of(&QuerySupplyOfRequest {
+                denom: "usdt".to_string(),
+            })
+            .unwrap()
+            .amount
+            .unwrap();
+        assert_eq!(supply.amount, 1_000u128.to_string());
+
+        let mut owners = bank
+            .query_denom_owners(&QueryDenomOwnersRequest {
+                denom: "usdt".to_string(),
+                pagination: None,
+            })
+            .unwrap()
+            .denom_owners
+            .into_iter()
+            .map(|owner| owner.address)
+            .collect::<Vec<_>>();
+        owners.sort();
+
+        let mut expected_owners = vec![
+            signer.address(),
+            receiver.address(),
+            other_receiver.address(),
+        ];
+        expected_owners.sort();
+        assert_eq!(owners, expected_owners);
+
+        // `init_account` registers metadata for unknown denoms
+        let metadata = bank
+            .query_denom_metadata(&QueryDenomMetadataRequest {
+                denom: "usdt".to_string(),
+            })
+            .unwrap()
+            .metadata
+            .unwrap();
+        assert_eq!(metadata.base, "usdt");
+
+        let params = bank
+            .query_params(&QueryParamsRequest {})
+            .unwrap()
+            .params
+            .unwrap();
+        assert!(params.default_send_enabled);
     }
 }
@@ -1,8 +1,49 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal256, StdResult};
+use injective_std::types::cosmos::bank::v1beta1::QueryDenomMetadataRequest;
 use injective_std::types::injective::exchange::v1beta1;
+use injective_std::types::injective::oracle::v1beta1::{
+    MsgRelayProviderPrices, MsgRelayProviderPricesResponse,
+};
+use test_tube_inj::account::{Account, SigningAccount};
 use test_tube_inj::module::Module;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::RunnerResult;
 use test_tube_inj::runner::Runner;
 use test_tube_inj::{fn_execute, fn_query};
 
+use crate::{default_subaccount_id, Bank, InjectiveTestApp, MarketDecimals};
+
+/// Aggregated quantity resting at a price, in human readable units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceLevel {
+    pub price: Decimal256,
+    pub quantity: Decimal256,
+}
+
+impl PriceLevel {
+    /// Parse a price level from decimal strings, e.g. `PriceLevel::try_new("9.5", "1")`.
+    pub fn try_new(price: &str, quantity: &str) -> StdResult<Self> {
+        Ok(Self {
+            price: Decimal256::from_str(price)?,
+            quantity: Decimal256::from_str(quantity)?,
+        })
+    }
+
+    /// Like [`PriceLevel::try_new`], panicking if `price` or `quantity` isn't a decimal.
+    pub fn new(price: &str, quantity: &str) -> Self {
+        Self::try_new(price, quantity).expect("price and quantity must be decimals")
+    }
+}
+
+/// Orderbook of a market with its best price levels first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderbookSnapshot {
+    pub buys: Vec<PriceLevel>,
+    pub sells: Vec<PriceLevel>,
+}
+
 pub struct Exchange<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -29,6 +70,22 @@ where
         pub create_derivative_limit_order: v1beta1::MsgCreateDerivativeLimitOrder => v1beta1::MsgCreateDerivativeLimitOrderResponse
     }
 
+    fn_execute! {
+        pub create_spot_market_order: v1beta1::MsgCreateSpotMarketOrder => v1beta1::MsgCreateSpotMarketOrderResponse
+    }
+
+    fn_execute! {
+        pub create_derivative_market_order: v1beta1::MsgCreateDerivativeMarketOrder => v1beta1::MsgCreateDerivativeMarketOrderResponse
+    }
+
+    fn_execute! {
+        pub batch_create_spot_limit_orders: v1beta1::MsgBatchCreateSpotLimitOrders => v1beta1::MsgBatchCreateSpotLimitOrdersResponse
+    }
+
+    fn_execute! {
+        pub batch_create_derivative_limit_orders: v1beta1::MsgBatchCreateDerivativeLimitOrders => v1beta1::MsgBatchCreateDerivativeLimitOrdersResponse
+    }
+
     fn_execute! {
         pub cancel_spot_order: v1beta1::MsgCancelSpotOrder => v1beta1::MsgCancelSpotOrderResponse
     }
@@ -37,14 +94,74 @@ where
         pub cancel_derivative_order: v1beta1::MsgCancelDerivativeOrder => v1beta1::MsgCancelDerivativeOrderResponse
     }
 
+    fn_execute! {
+        pub batch_cancel_spot_orders: v1beta1::MsgBatchCancelSpotOrders => v1beta1::MsgBatchCancelSpotOrdersResponse
+    }
+
+    fn_execute! {
+        pub batch_cancel_derivative_orders: v1beta1::MsgBatchCancelDerivativeOrders => v1beta1::MsgBatchCancelDerivativeOrdersResponse
+    }
+
     fn_execute! {
         pub batch_update_orders: v1beta1::MsgBatchUpdateOrders => v1beta1::MsgBatchUpdateOrdersResponse
     }
 
+    fn_execute! {
+        pub subaccount_transfer: v1beta1::MsgSubaccountTransfer => v1beta1::MsgSubaccountTransferResponse
+    }
+
+    fn_execute! {
+        pub external_transfer: v1beta1::MsgExternalTransfer => v1beta1::MsgExternalTransferResponse
+    }
+
+    fn_execute! {
+        pub increase_position_margin: v1beta1::MsgIncreasePositionMargin => v1beta1::MsgIncreasePositionMarginResponse
+    }
+
+    fn_execute! {
+        pub decrease_position_margin: v1beta1::MsgDecreasePositionMargin => v1beta1::MsgDecreasePositionMarginResponse
+    }
+
+    fn_execute! {
+        pub liquidate_position: v1beta1::MsgLiquidatePosition => v1beta1::MsgLiquidatePositionResponse
+    }
+
+    fn_execute! {
+        pub emergency_settle_market: v1beta1::MsgEmergencySettleMarket => v1beta1::MsgEmergencySettleMarketResponse
+    }
+
+    fn_execute! {
+        pub rewards_opt_out: v1beta1::MsgRewardsOptOut => v1beta1::MsgRewardsOptOutResponse
+    }
+
     fn_execute! {
         pub instant_perpetual_market_launch: v1beta1::MsgInstantPerpetualMarketLaunch => v1beta1::MsgInstantPerpetualMarketLaunchResponse
     }
 
+    fn_execute! {
+        pub instant_expiry_futures_market_launch: v1beta1::MsgInstantExpiryFuturesMarketLaunch => v1beta1::MsgInstantExpiryFuturesMarketLaunchResponse
+    }
+
+    fn_execute! {
+        pub instant_binary_options_market_launch: v1beta1::MsgInstantBinaryOptionsMarketLaunch => v1beta1::MsgInstantBinaryOptionsMarketLaunchResponse
+    }
+
+    fn_execute! {
+        pub create_binary_options_limit_order: v1beta1::MsgCreateBinaryOptionsLimitOrder => v1beta1::MsgCreateBinaryOptionsLimitOrderResponse
+    }
+
+    fn_execute! {
+        pub create_binary_options_market_order: v1beta1::MsgCreateBinaryOptionsMarketOrder => v1beta1::MsgCreateBinaryOptionsMarketOrderResponse
+    }
+
+    fn_execute! {
+        pub cancel_binary_options_order: v1beta1::MsgCancelBinaryOptionsOrder => v1beta1::MsgCancelBinaryOptionsOrderResponse
+    }
+
+    fn_execute! {
+        pub admin_update_binary_options_market: v1beta1::MsgAdminUpdateBinaryOptionsMarket => v1beta1::MsgAdminUpdateBinaryOptionsMarketResponse
+    }
+
     fn_execute! {
         pub privileged_execute_contract: v1beta1::MsgPrivilegedExecuteContract => v1beta1::MsgPrivilegedExecuteContractResponse
     }
@@ -81,6 +198,14 @@ where
         pub query_derivative_mid_price_and_tob ["/injective.exchange.v1beta1.Query/DerivativeMidPriceAndTOB"]: v1beta1::QueryDerivativeMidPriceAndTobRequest => v1beta1::QueryDerivativeMidPriceAndTobResponse
     }
 
+    fn_query! {
+        pub query_expiry_futures_market_info ["/injective.exchange.v1beta1.Query/ExpiryFuturesMarketInfo"]: v1beta1::QueryExpiryFuturesMarketInfoRequest => v1beta1::QueryExpiryFuturesMarketInfoResponse
+    }
+
+    fn_query! {
+        pub query_binary_options_markets ["/injective.exchange.v1beta1.Query/BinaryOptionsMarkets"]: v1beta1::QueryBinaryOptionsMarketsRequest => v1beta1::QueryBinaryOptionsMarketsResponse
+    }
+
     fn_query! {
         pub query_subaccount_deposits ["/injective.exchange.v1beta1.Query/SubaccountDeposits"]: v1beta1::QuerySubaccountDepositsRequest => v1beta1::QuerySubaccountDepositsResponse
     }
@@ -118,29 +243,414 @@ where
     }
 
     fn_query! {
-        pub query_exchange_module_state ["/injective.exchange.v1beta1.Query/ModuleStateRequest"]: v1beta1::QueryModuleStateRequest => v1beta1::QueryModuleStateResponse
+        pub query_exchange_module_state ["/injective.exchange.v1beta1.Query/ExchangeModuleState"]: v1beta1::QueryModuleStateRequest => v1beta1::QueryModuleStateResponse
     }
 
     fn_query! {
         pub query_is_opted_out_of_rewards ["/injective.exchange.v1beta1.Query/IsOptedOutOfRewards"]: v1beta1::QueryIsOptedOutOfRewardsRequest => v1beta1::QueryIsOptedOutOfRewardsResponse
     }
+
+    fn_query! {
+        pub query_exchange_params ["/injective.exchange.v1beta1.Query/QueryExchangeParams"]: v1beta1::QueryExchangeParamsRequest => v1beta1::QueryExchangeParamsResponse
+    }
+
+    fn_query! {
+        pub query_subaccount_deposit ["/injective.exchange.v1beta1.Query/SubaccountDeposit"]: v1beta1::QuerySubaccountDepositRequest => v1beta1::QuerySubaccountDepositResponse
+    }
+
+    fn_query! {
+        pub query_exchange_balances ["/injective.exchange.v1beta1.Query/ExchangeBalances"]: v1beta1::QueryExchangeBalancesRequest => v1beta1::QueryExchangeBalancesResponse
+    }
+
+    fn_query! {
+        pub query_aggregate_volume ["/injective.exchange.v1beta1.Query/AggregateVolume"]: v1beta1::QueryAggregateVolumeRequest => v1beta1::QueryAggregateVolumeResponse
+    }
+
+    fn_query! {
+        pub query_aggregate_volumes ["/injective.exchange.v1beta1.Query/AggregateVolumes"]: v1beta1::QueryAggregateVolumesRequest => v1beta1::QueryAggregateVolumesResponse
+    }
+
+    fn_query! {
+        pub query_aggregate_market_volume ["/injective.exchange.v1beta1.Query/AggregateMarketVolume"]: v1beta1::QueryAggregateMarketVolumeRequest => v1beta1::QueryAggregateMarketVolumeResponse
+    }
+
+    fn_query! {
+        pub query_aggregate_market_volumes ["/injective.exchange.v1beta1.Query/AggregateMarketVolumes"]: v1beta1::QueryAggregateMarketVolumesRequest => v1beta1::QueryAggregateMarketVolumesResponse
+    }
+
+    fn_query! {
+        pub query_denom_decimal ["/injective.exchange.v1beta1.Query/DenomDecimal"]: v1beta1::QueryDenomDecimalRequest => v1beta1::QueryDenomDecimalResponse
+    }
+
+    fn_query! {
+        pub query_denom_decimals ["/injective.exchange.v1beta1.Query/DenomDecimals"]: v1beta1::QueryDenomDecimalsRequest => v1beta1::QueryDenomDecimalsResponse
+    }
+
+    fn_query! {
+        pub query_full_spot_markets ["/injective.exchange.v1beta1.Query/FullSpotMarkets"]: v1beta1::QueryFullSpotMarketsRequest => v1beta1::QueryFullSpotMarketsResponse
+    }
+
+    fn_query! {
+        pub query_full_spot_market ["/injective.exchange.v1beta1.Query/FullSpotMarket"]: v1beta1::QueryFullSpotMarketRequest => v1beta1::QueryFullSpotMarketResponse
+    }
+
+    fn_query! {
+        pub query_account_address_spot_orders ["/injective.exchange.v1beta1.Query/AccountAddressSpotOrders"]: v1beta1::QueryAccountAddressSpotOrdersRequest => v1beta1::QueryAccountAddressSpotOrdersResponse
+    }
+
+    fn_query! {
+        pub query_spot_orders_by_hashes ["/injective.exchange.v1beta1.Query/SpotOrdersByHashes"]: v1beta1::QuerySpotOrdersByHashesRequest => v1beta1::QuerySpotOrdersByHashesResponse
+    }
+
+    fn_query! {
+        pub query_subaccount_orders ["/injective.exchange.v1beta1.Query/SubaccountOrders"]: v1beta1::QuerySubaccountOrdersRequest => v1beta1::QuerySubaccountOrdersResponse
+    }
+
+    fn_query! {
+        pub query_trader_spot_transient_orders ["/injective.exchange.v1beta1.Query/TraderSpotTransientOrders"]: v1beta1::QueryTraderSpotOrdersRequest => v1beta1::QueryTraderSpotOrdersResponse
+    }
+
+    fn_query! {
+        pub query_account_address_derivative_orders ["/injective.exchange.v1beta1.Query/AccountAddressDerivativeOrders"]: v1beta1::QueryAccountAddressDerivativeOrdersRequest => v1beta1::QueryAccountAddressDerivativeOrdersResponse
+    }
+
+    fn_query! {
+        pub query_derivative_orders_by_hashes ["/injective.exchange.v1beta1.Query/DerivativeOrdersByHashes"]: v1beta1::QueryDerivativeOrdersByHashesRequest => v1beta1::QueryDerivativeOrdersByHashesResponse
+    }
+
+    fn_query! {
+        pub query_trader_derivative_transient_orders ["/injective.exchange.v1beta1.Query/TraderDerivativeTransientOrders"]: v1beta1::QueryTraderDerivativeOrdersRequest => v1beta1::QueryTraderDerivativeOrdersResponse
+    }
+
+    fn_query! {
+        pub query_trader_derivative_conditional_orders ["/injective.exchange.v1beta1.Query/TraderDerivativeConditionalOrders"]: v1beta1::QueryTraderDerivativeConditionalOrdersRequest => v1beta1::QueryTraderDerivativeConditionalOrdersResponse
+    }
+
+    fn_query! {
+        pub query_derivative_market_address ["/injective.exchange.v1beta1.Query/DerivativeMarketAddress"]: v1beta1::QueryDerivativeMarketAddressRequest => v1beta1::QueryDerivativeMarketAddressResponse
+    }
+
+    fn_query! {
+        pub query_subaccount_trade_nonce ["/injective.exchange.v1beta1.Query/SubaccountTradeNonce"]: v1beta1::QuerySubaccountTradeNonceRequest => v1beta1::QuerySubaccountTradeNonceResponse
+    }
+
+    fn_query! {
+        pub query_perpetual_market_info ["/injective.exchange.v1beta1.Query/PerpetualMarketInfo"]: v1beta1::QueryPerpetualMarketInfoRequest => v1beta1::QueryPerpetualMarketInfoResponse
+    }
+
+    fn_query! {
+        pub query_perpetual_market_funding ["/injective.exchange.v1beta1.Query/PerpetualMarketFunding"]: v1beta1::QueryPerpetualMarketFundingRequest => v1beta1::QueryPerpetualMarketFundingResponse
+    }
+
+    fn_query! {
+        pub query_subaccount_order_metadata ["/injective.exchange.v1beta1.Query/SubaccountOrderMetadata"]: v1beta1::QuerySubaccountOrderMetadataRequest => v1beta1::QuerySubaccountOrderMetadataResponse
+    }
+
+    fn_query! {
+        pub query_trade_reward_points ["/injective.exchange.v1beta1.Query/TradeRewardPoints"]: v1beta1::QueryTradeRewardPointsRequest => v1beta1::QueryTradeRewardPointsResponse
+    }
+
+    fn_query! {
+        pub query_pending_trade_reward_points ["/injective.exchange.v1beta1.Query/PendingTradeRewardPoints"]: v1beta1::QueryTradeRewardPointsRequest => v1beta1::QueryTradeRewardPointsResponse
+    }
+
+    fn_query! {
+        pub query_trade_reward_campaign ["/injective.exchange.v1beta1.Query/TradeRewardCampaign"]: v1beta1::QueryTradeRewardCampaignRequest => v1beta1::QueryTradeRewardCampaignResponse
+    }
+
+    fn_query! {
+        pub query_fee_discount_account_info ["/injective.exchange.v1beta1.Query/FeeDiscountAccountInfo"]: v1beta1::QueryFeeDiscountAccountInfoRequest => v1beta1::QueryFeeDiscountAccountInfoResponse
+    }
+
+    fn_query! {
+        pub query_fee_discount_schedule ["/injective.exchange.v1beta1.Query/FeeDiscountSchedule"]: v1beta1::QueryFeeDiscountScheduleRequest => v1beta1::QueryFeeDiscountScheduleResponse
+    }
+
+    fn_query! {
+        pub query_fee_discount_tier_statistics ["/injective.exchange.v1beta1.Query/FeeDiscountTierStatistics"]: v1beta1::QueryFeeDiscountTierStatisticsRequest => v1beta1::QueryFeeDiscountTierStatisticsResponse
+    }
+
+    fn_query! {
+        pub query_balance_mismatches ["/injective.exchange.v1beta1.Query/BalanceMismatches"]: v1beta1::QueryBalanceMismatchesRequest => v1beta1::QueryBalanceMismatchesResponse
+    }
+
+    fn_query! {
+        pub query_balance_with_balance_holds ["/injective.exchange.v1beta1.Query/BalanceWithBalanceHolds"]: v1beta1::QueryBalanceWithBalanceHoldsRequest => v1beta1::QueryBalanceWithBalanceHoldsResponse
+    }
+
+    fn_query! {
+        pub query_mito_vault_infos ["/injective.exchange.v1beta1.Query/MitoVaultInfos"]: v1beta1::MitoVaultInfosRequest => v1beta1::MitoVaultInfosResponse
+    }
+
+    fn_query! {
+        pub query_market_id_from_vault ["/injective.exchange.v1beta1.Query/QueryMarketIDFromVault"]: v1beta1::QueryMarketIdFromVaultRequest => v1beta1::QueryMarketIdFromVaultResponse
+    }
+
+    fn_query! {
+        pub query_historical_trade_records ["/injective.exchange.v1beta1.Query/HistoricalTradeRecords"]: v1beta1::QueryHistoricalTradeRecordsRequest => v1beta1::QueryHistoricalTradeRecordsResponse
+    }
+
+    fn_query! {
+        pub query_opted_out_of_rewards_accounts ["/injective.exchange.v1beta1.Query/OptedOutOfRewardsAccounts"]: v1beta1::QueryOptedOutOfRewardsAccountsRequest => v1beta1::QueryOptedOutOfRewardsAccountsResponse
+    }
+
+    fn_query! {
+        pub query_market_volatility ["/injective.exchange.v1beta1.Query/MarketVolatility"]: v1beta1::QueryMarketVolatilityRequest => v1beta1::QueryMarketVolatilityResponse
+    }
+
+    fn_query! {
+        pub query_market_atomic_execution_fee_multiplier ["/injective.exchange.v1beta1.Query/MarketAtomicExecutionFeeMultiplier"]: v1beta1::QueryMarketAtomicExecutionFeeMultiplierRequest => v1beta1::QueryMarketAtomicExecutionFeeMultiplierResponse
+    }
+}
+
+impl<'a, R> Exchange<'a, R>
+where
+    R: Runner<'a>,
+{
+    /// Place resting `bids` and `asks`, given in human readable units, from the default
+    /// subaccount of `maker` within a single `MsgBatchUpdateOrders`.
+    ///
+    /// Derivative orders are placed without leverage, i.e. with a margin of price * quantity.
+    pub fn seed_orderbook(
+        &self,
+        market_id: &str,
+        bids: &[PriceLevel],
+        asks: &[PriceLevel],
+        maker: &SigningAccount,
+    ) -> RunnerResult<v1beta1::MsgBatchUpdateOrdersResponse> {
+        let (is_spot, decimals) = self.market_kind_and_decimals(market_id)?;
+        let subaccount_id = default_subaccount_id(maker);
+
+        let orders = bids
+            .iter()
+            .map(|level| (v1beta1::OrderType::Buy, level))
+            .chain(asks.iter().map(|level| (v1beta1::OrderType::Sell, level)))
+            .map(|(order_type, level)| {
+                let price = decimals.price_to_chain_decimal(level.price)?;
+                let quantity = decimals.quantity_to_chain_decimal(level.quantity)?;
+                let margin = price.checked_mul(quantity)?.atomics().to_string();
+
+                Ok((
+                    order_type as i32,
+                    v1beta1::OrderInfo {
+                        subaccount_id: subaccount_id.clone(),
+                        fee_recipient: maker.address(),
+                        price: price.atomics().to_string(),
+                        quantity: quantity.atomics().to_string(),
+                        cid: "".to_string(),
+                    },
+                    margin,
+                ))
+            })
+            .collect::<StdResult<Vec<_>>>()
+            .map_err(|e| RunnerError::GenericError(e.to_string()))?;
+
+        let mut msg = v1beta1::MsgBatchUpdateOrders {
+            sender: maker.address(),
+            ..Default::default()
+        };
+        for (order_type, order_info, margin) in orders {
+            if is_spot {
+                msg.spot_orders_to_create.push(v1beta1::SpotOrder {
+                    market_id: market_id.to_string(),
+                    order_info: Some(order_info),
+                    order_type,
+                    trigger_price: "".to_string(),
+                });
+            } else {
+                msg.derivative_orders_to_create
+                    .push(v1beta1::DerivativeOrder {
+                        market_id: market_id.to_string(),
+                        order_info: Some(order_info),
+                        order_type,
+                        margin,
+                        trigger_price: "".to_string(),
+                    });
+            }
+        }
+
+        Ok(self.batch_update_orders(msg, maker)?.data)
+    }
+
+    /// Aggregated orderbook of a spot or derivative market, in human readable units.
+    pub fn orderbook_snapshot(&self, market_id: &str) -> RunnerResult<OrderbookSnapshot> {
+        let (is_spot, decimals) = self.market_kind_and_decimals(market_id)?;
+
+        let (buys, sells) = if is_spot {
+            let res = self.query_spot_market_orderbook(&v1beta1::QuerySpotOrderbookRequest {
+                market_id: market_id.to_string(),
+                ..Default::default()
+            })?;
+            (res.buys_price_level, res.sells_price_level)
+        } else {
+            let res = self.query_derivative_market_orderbook(
+                &v1beta1::QueryDerivativeOrderbookRequest {
+                    market_id: market_id.to_string(),
+                    ..Default::default()
+                },
+            )?;
+            (res.buys_price_level, res.sells_price_level)
+        };
+
+        let to_price_levels = |levels: Vec<v1beta1::Level>| {
+            levels
+                .into_iter()
+                .map(|level| {
+                    Ok(PriceLevel {
+                        price: decimals.price_from_chain_decimal(&level.p)?,
+                        quantity: decimals.quantity_from_chain_decimal(&level.q)?,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()
+                .map_err(|e| RunnerError::GenericError(e.to_string()))
+        };
+
+        Ok(OrderbookSnapshot {
+            buys: to_price_levels(buys)?,
+            sells: to_price_levels(sells)?,
+        })
+    }
+
+    /// Decimals of an active spot or derivative market, to be used with the [`MarketDecimals`]
+    /// conversions. They are read from the bank denom metadata of the market denoms, which
+    /// must declare their decimals.
+    pub fn query_market_decimals(&self, market_id: &str) -> RunnerResult<MarketDecimals> {
+        self.market_kind_and_decimals(market_id)
+            .map(|(_, decimals)| decimals)
+    }
+
+    // whether the market is a spot market, along with its decimals
+    fn market_kind_and_decimals(&self, market_id: &str) -> RunnerResult<(bool, MarketDecimals)> {
+        // the list queries filter by id instead of failing for markets of the other kind
+        let spot_market = self
+            .query_spot_markets(&v1beta1::QuerySpotMarketsRequest {
+                status: "".to_string(),
+                market_ids: vec![market_id.to_string()],
+            })?
+            .markets
+            .into_iter()
+            .next();
+        if let Some(market) = spot_market {
+            return Ok((
+                true,
+                MarketDecimals::spot(
+                    self.denom_decimals(&market.base_denom)?,
+                    self.denom_decimals(&market.quote_denom)?,
+                ),
+            ));
+        }
+
+        let derivative_market = self
+            .query_derivative_markets(&v1beta1::QueryDerivativeMarketsRequest {
+                status: "".to_string(),
+                market_ids: vec![market_id.to_string()],
+                with_mid_price_and_tob: false,
+            })?
+            .markets
+            .into_iter()
+            .find_map(|full_market| full_market.market)
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("market {} not found", market_id),
+            })?;
+
+        Ok((
+            false,
+            MarketDecimals::derivative(self.denom_decimals(&derivative_market.quote_denom)?),
+        ))
+    }
+
+    fn denom_decimals(&self, denom: &str) -> RunnerResult<u32> {
+        let metadata = Bank::new(self.runner)
+            .query_denom_metadata(&QueryDenomMetadataRequest {
+                denom: denom.to_string(),
+            })?
+            .metadata
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("denom {} has no metadata", denom),
+            })?;
+
+        // token factory denoms only carry a zero exponent unit next to their decimals
+        let decimals = metadata.decimals.max(
+            metadata
+                .denom_units
+                .iter()
+                .map(|unit| unit.exponent)
+                .max()
+                .unwrap_or(0),
+        );
+        if decimals == 0 {
+            return Err(RunnerError::GenericError(format!(
+                "denom {} declares no decimals",
+                denom
+            )));
+        }
+
+        Ok(decimals)
+    }
+}
+
+impl<'a> Exchange<'a, InjectiveTestApp> {
+    /// Settle the binary options market `market_id` at `settlement_price`.
+    ///
+    /// The price is relayed for the market's provider oracle symbol by `relayer`, which
+    /// must hold the relayer privilege for that provider, and the block time is then
+    /// advanced past the settlement timestamp of the market.
+    pub fn settle_binary_options_market(
+        &self,
+        market_id: &str,
+        settlement_price: &str,
+        relayer: &SigningAccount,
+    ) -> RunnerResult<()> {
+        let market = self
+            .query_binary_options_markets(&v1beta1::QueryBinaryOptionsMarketsRequest {
+                status: "".to_string(),
+            })?
+            .markets
+            .into_iter()
+            .find(|market| market.market_id == market_id)
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("binary options market {} not found", market_id),
+            })?;
+
+        self.runner.execute::<_, MsgRelayProviderPricesResponse>(
+            MsgRelayProviderPrices {
+                sender: relayer.address(),
+                provider: market.oracle_provider,
+                symbols: vec![market.oracle_symbol],
+                prices: vec![settlement_price.to_string()],
+            },
+            MsgRelayProviderPrices::TYPE_URL,
+            relayer,
+        )?;
+
+        let remaining = (market.settlement_timestamp - self.runner.get_block_time_seconds()).max(0);
+        self.runner.increase_time(remaining as u64 + 1);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{Addr, Coin};
-    use injective_cosmwasm::{
-        checked_address_to_subaccount_id, get_default_subaccount_id_for_checked_address,
-    };
+    use cosmwasm_std::Coin;
     use injective_std::shim::Any;
     use injective_std::types::{
         cosmos::authz::v1beta1::{GenericAuthorization, Grant, MsgExec, MsgGrant},
         cosmos::base::v1beta1::Coin as SDKCoin,
+        cosmos::gov::v1::MsgExecLegacyContent,
         injective::exchange::v1beta1,
+        injective::insurance::v1beta1::MsgCreateInsuranceFund,
+        injective::oracle::v1beta1::{
+            GrantProviderPrivilegeProposal, MsgRelayPriceFeedPrice, OracleType,
+        },
     };
     use prost::Message;
 
-    use crate::{Account, Authz, Exchange, InjectiveTestApp};
+    use crate::{
+        default_subaccount_id, subaccount_id, Account, Authz, Exchange, ExchangeFixture,
+        GovWithAppAccess, InjectiveTestApp, Insurance, MarketDecimals, MarketSpec, Oracle,
+        OrderbookSnapshot, PriceLevel, RunnerError, TokenFactory, GOV_MODULE_ADDRESS,
+    };
     use test_tube_inj::Module;
 
     #[test]
@@ -249,11 +759,7 @@ mod tests {
                             "0xd5a22be807011d5e42d5b77da3f417e22676efae494109cd01c242ad46630115"
                                 .to_string(),
                         order_info: Some(v1beta1::OrderInfo {
-                            subaccount_id: get_default_subaccount_id_for_checked_address(
-                                &Addr::unchecked(signer.address()),
-                            )
-                            .as_str()
-                            .to_string(),
+                            subaccount_id: default_subaccount_id(&signer),
                             fee_recipient: signer.address(),
                             price: "1000000000000000000".to_string(),
                             quantity: "10000000000000000000".to_string(),
@@ -276,11 +782,7 @@ mod tests {
                             "0xd5a22be807011d5e42d5b77da3f417e22676efae494109cd01c242ad46630115"
                                 .to_string(),
                         order_info: Some(v1beta1::OrderInfo {
-                            subaccount_id: get_default_subaccount_id_for_checked_address(
-                                &Addr::unchecked(trader.address()),
-                            )
-                            .as_str()
-                            .to_string(),
+                            subaccount_id: default_subaccount_id(&trader),
                             fee_recipient: trader.address(),
                             price: "2000000000000000000".to_string(),
                             quantity: "10000000000000000000".to_string(),
@@ -320,10 +822,7 @@ mod tests {
             .query_trader_spot_orders(&v1beta1::QueryTraderSpotOrdersRequest {
                 market_id: "0xd5a22be807011d5e42d5b77da3f417e22676efae494109cd01c242ad46630115"
                     .to_string(),
-                subaccount_id: get_default_subaccount_id_for_checked_address(&Addr::unchecked(
-                    trader.address(),
-                ))
-                .to_string(),
+                subaccount_id: default_subaccount_id(&trader),
             })
             .unwrap();
 
@@ -361,11 +860,7 @@ mod tests {
                     market_id: "0xd5a22be807011d5e42d5b77da3f417e22676efae494109cd01c242ad46630115"
                         .to_string(),
                     order_info: Some(v1beta1::OrderInfo {
-                        subaccount_id: get_default_subaccount_id_for_checked_address(
-                            &Addr::unchecked(trader.address()),
-                        )
-                        .as_str()
-                        .to_string(),
+                        subaccount_id: default_subaccount_id(&trader),
                         fee_recipient: trader.address(),
                         price: "2200000000000000000".to_string(),
                         quantity: "10000000000000000000".to_string(),
@@ -396,10 +891,7 @@ mod tests {
             .query_trader_spot_orders(&v1beta1::QueryTraderSpotOrdersRequest {
                 market_id: "0xd5a22be807011d5e42d5b77da3f417e22676efae494109cd01c242ad46630115"
                     .to_string(),
-                subaccount_id: get_default_subaccount_id_for_checked_address(&Addr::unchecked(
-                    trader.address(),
-                ))
-                .to_string(),
+                subaccount_id: default_subaccount_id(&trader),
             })
             .unwrap();
 
@@ -410,11 +902,7 @@ mod tests {
             .deposit(
                 v1beta1::MsgDeposit {
                     sender: depositor.address(),
-                    subaccount_id: checked_address_to_subaccount_id(
-                        &Addr::unchecked(depositor.address()),
-                        1u32,
-                    )
-                    .to_string(),
+                    subaccount_id: subaccount_id(&depositor, 1),
                     amount: Some(SDKCoin {
                         amount: 1u128.to_string(),
                         denom: "inj".to_string(),
@@ -426,11 +914,7 @@ mod tests {
 
         let response = exchange
             .query_subaccount_deposits(&v1beta1::QuerySubaccountDepositsRequest {
-                subaccount_id: checked_address_to_subaccount_id(
-                    &Addr::unchecked(depositor.address()),
-                    1u32,
-                )
-                .to_string(),
+                subaccount_id: subaccount_id(&depositor, 1),
                 subaccount: None,
             })
             .unwrap();
@@ -447,11 +931,7 @@ mod tests {
             .withdraw(
                 v1beta1::MsgWithdraw {
                     sender: depositor.address(),
-                    subaccount_id: checked_address_to_subaccount_id(
-                        &Addr::unchecked(depositor.address()),
-                        1u32,
-                    )
-                    .to_string(),
+                    subaccount_id: subaccount_id(&depositor, 1),
                     amount: Some(SDKCoin {
                         amount: 1u128.to_string(),
                         denom: "inj".to_string(),
@@ -463,11 +943,7 @@ mod tests {
 
         let response = exchange
             .query_subaccount_deposits(&v1beta1::QuerySubaccountDepositsRequest {
-                subaccount_id: checked_address_to_subaccount_id(
-                    &Addr::unchecked(depositor.address()),
-                    1u32,
-                )
-                .to_string(),
+                subaccount_id: subaccount_id(&depositor, 1),
                 subaccount: None,
             })
             .unwrap();
@@ -480,4 +956,1021 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn exchange_market_and_batch_orders() {
+        let app = InjectiveTestApp::new();
+        let maker = app
+            .init_account(&[
+                Coin::new(10_000_000_000_000_000_000_000u128, "inj"),
+                Coin::new(100_000_000_000_000_000_000u128, "usdt"),
+            ])
+            .unwrap();
+        let taker = app
+            .init_account(&[
+                Coin::new(10_000_000_000_000_000_000_000u128, "inj"),
+                Coin::new(100_000_000_000_000_000_000u128, "usdt"),
+            ])
+            .unwrap();
+
+        let exchange = Exchange::new(&app);
+
+        let market_id = exchange
+            .instant_spot_market_launch(
+                v1beta1::MsgInstantSpotMarketLaunch {
+                    sender: maker.address(),
+                    ticker: "INJ/USDT".to_owned(),
+                    base_denom: "inj".to_owned(),
+                    quote_denom: "usdt".to_owned(),
+                    min_price_tick_size: "10000".to_owned(),
+                    min_quantity_tick_size: "100000".to_owned(),
+                    min_notional: "1".to_owned(),
+                },
+                &maker,
+            )
+            .map(|_| {
+                exchange
+                    .query_spot_markets(&v1beta1::QuerySpotMarketsRequest {
+                        status: "Active".to_owned(),
+                        market_ids: vec![],
+                    })
+                    .unwrap()
+                    .markets[0]
+                    .market_id
+                    .clone()
+            })
+            .unwrap();
+
+        let maker_subaccount_id = default_subaccount_id(&maker);
+        let taker_subaccount_id = default_subaccount_id(&taker);
+
+        let sell_order = |price: &str| v1beta1::SpotOrder {
+            market_id: market_id.clone(),
+            order_info: Some(v1beta1::OrderInfo {
+                subaccount_id: maker_subaccount_id.clone(),
+                fee_recipient: maker.address(),
+                price: price.to_string(),
+                quantity: "10000000000000000000".to_string(),
+                cid: "".to_string(),
+            }),
+            order_type: 2i32,
+            trigger_price: "".to_string(),
+        };
+
+        let res = exchange
+            .batch_create_spot_limit_orders(
+                v1beta1::MsgBatchCreateSpotLimitOrders {
+                    sender: maker.address(),
+                    orders: vec![
+                        sell_order("2000000000000000000"),
+                        sell_order("2100000000000000000"),
+                    ],
+                },
+                &maker,
+            )
+            .unwrap();
+        assert_eq!(res.data.order_hashes.len(), 2);
+
+        exchange
+            .create_spot_market_order(
+                v1beta1::MsgCreateSpotMarketOrder {
+                    sender: taker.address(),
+                    order: Some(v1beta1::SpotOrder {
+                        market_id: market_id.clone(),
+                        order_info: Some(v1beta1::OrderInfo {
+                            subaccount_id: taker_subaccount_id,
+                            fee_recipient: taker.address(),
+                            price: "2000000000000000000".to_string(),
+                            quantity: "10000000000000000000".to_string(),
+                            cid: "".to_string(),
+                        }),
+                        order_type: 1i32,
+                        trigger_price: "".to_string(),
+                    }),
+                },
+                &taker,
+            )
+            .unwrap();
+
+        // the best ask was taken by the market order
+        let resting_orders = exchange
+            .query_trader_spot_orders(&v1beta1::QueryTraderSpotOrdersRequest {
+                market_id: market_id.clone(),
+                subaccount_id: maker_subaccount_id.clone(),
+            })
+            .unwrap()
+            .orders;
+        assert_eq!(resting_orders.len(), 1);
+        assert_eq!(resting_orders[0].price, "2100000000000000000");
+
+        let res = exchange
+            .batch_cancel_spot_orders(
+                v1beta1::MsgBatchCancelSpotOrders {
+                    sender: maker.address(),
+                    data: vec![v1beta1::OrderData {
+                        market_id: market_id.clone(),
+                        subaccount_id: maker_subaccount_id.clone(),
+                        order_hash: resting_orders[0].order_hash.clone(),
+                        order_mask: 1i32,
+                        cid: "".to_string(),
+                    }],
+                },
+                &maker,
+            )
+            .unwrap();
+        assert_eq!(res.data.success, vec![true]);
+
+        let resting_orders = exchange
+            .query_trader_spot_orders(&v1beta1::QueryTraderSpotOrdersRequest {
+                market_id,
+                subaccount_id: maker_subaccount_id,
+            })
+            .unwrap()
+            .orders;
+        assert!(resting_orders.is_empty());
+    }
+
+    #[test]
+    fn expiry_futures_market_launch_and_order() {
+        let app = InjectiveTestApp::new();
+        // the perpetual market sets up the ATOM/USDT price feed
+        let fixture = ExchangeFixture::builder(&app)
+            .denom("atom", 6)
+            .denom("usdt", 6)
+            .traders(1, 1_000_000_000_000)
+            .perpetual_market(
+                // 10 USDT in chain units
+                MarketSpec::new("atom", "usdt").with_oracle_price("10000000000000000000000000"),
+            )
+            .build()
+            .unwrap();
+        let (owner, trader) = (&fixture.owner, &fixture.traders[0]);
+        let usdt = fixture.denom("usdt").to_string();
+
+        let exchange = Exchange::new(&app);
+
+        // expiry futures markets need an insurance fund for their expiry
+        let ticker = "ATOM/USDT-1D".to_string();
+        let expiry = app.get_block_time_seconds() + 86400;
+        TokenFactory::new(&app)
+            .mint_to(Coin::new(1_000_000u128, &usdt), &owner.address(), owner)
+            .unwrap();
+        Insurance::new(&app)
+            .create_insurance_fund(
+                MsgCreateInsuranceFund {
+                    sender: owner.address(),
+                    ticker: ticker.clone(),
+                    quote_denom: usdt.clone(),
+                    oracle_base: "atom".to_string(),
+                    oracle_quote: "usdt".to_string(),
+                    oracle_type: OracleType::PriceFeed as i32,
+                    expiry,
+                    initial_deposit: Some(SDKCoin {
+                        amount: "1000000".to_string(),
+                        denom: usdt.clone(),
+                    }),
+                },
+                owner,
+            )
+            .unwrap();
+
+        exchange
+            .instant_expiry_futures_market_launch(
+                v1beta1::MsgInstantExpiryFuturesMarketLaunch {
+                    sender: owner.address(),
+                    ticker: ticker.clone(),
+                    quote_denom: usdt.clone(),
+                    oracle_base: "atom".to_string(),
+                    oracle_quote: "usdt".to_string(),
+                    oracle_type: OracleType::PriceFeed as i32,
+                    oracle_scale_factor: 0,
+                    expiry,
+                    maker_fee_rate: "1000000000000000".to_string(),
+                    taker_fee_rate: "2000000000000000".to_string(),
+                    initial_margin_ratio: "50000000000000000".to_string(),
+                    maintenance_margin_ratio: "20000000000000000".to_string(),
+                    min_price_tick_size: "10000".to_string(),
+                    min_quantity_tick_size: "100000".to_string(),
+                    min_notional: "1".to_string(),
+                },
+                owner,
+            )
+            .unwrap();
+
+        let market_id = exchange
+            .query_derivative_markets(&v1beta1::QueryDerivativeMarketsRequest {
+                status: "Active".to_string(),
+                market_ids: vec![],
+                with_mid_price_and_tob: false,
+            })
+            .unwrap()
+            .markets
+            .into_iter()
+            .filter_map(|full_market| full_market.market)
+            .find(|market| market.ticker == ticker)
+            .unwrap()
+            .market_id;
+
+        let info = exchange
+            .query_expiry_futures_market_info(&v1beta1::QueryExpiryFuturesMarketInfoRequest {
+                market_id: market_id.clone(),
+            })
+            .unwrap()
+            .info
+            .unwrap();
+        assert_eq!(info.expiration_timestamp, expiry);
+
+        // a resting bid of 1 ATOM at 10 USDT without leverage
+        exchange
+            .create_derivative_limit_order(
+                v1beta1::MsgCreateDerivativeLimitOrder {
+                    sender: trader.address(),
+                    order: Some(v1beta1::DerivativeOrder {
+                        market_id: market_id.clone(),
+                        order_info: Some(v1beta1::OrderInfo {
+                            subaccount_id: fixture.trader_subaccount_ids[0].clone(),
+                            fee_recipient: trader.address(),
+                            price: "10000000000000000000000000".to_string(),
+                            quantity: "1000000000000000000".to_string(),
+                            cid: "".to_string(),
+                        }),
+                        order_type: v1beta1::OrderType::Buy as i32,
+                        margin: "10000000000000000000000000".to_string(),
+                        trigger_price: "".to_string(),
+                    }),
+                },
+                trader,
+            )
+            .unwrap();
+
+        let orders = exchange
+            .query_trader_derivative_orders(&v1beta1::QueryTraderDerivativeOrdersRequest {
+                market_id,
+                subaccount_id: fixture.trader_subaccount_ids[0].clone(),
+            })
+            .unwrap()
+            .orders;
+        assert_eq!(orders.len(), 1);
+        assert!(orders[0].is_buy);
+    }
+
+    #[test]
+    fn binary_options_market_settlement() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[
+                Coin::new(100_000_000_000_000_000_000_000u128, "inj"),
+                Coin::new(100_000_000_000_000_000_000u128, "usdt"),
+            ])
+            .unwrap();
+
+        let exchange = Exchange::new(&app);
+
+        // register the oracle provider with the signer as relayer
+        GovWithAppAccess::new(&app)
+            .propose_and_execute(
+                MsgExecLegacyContent::TYPE_URL.to_string(),
+                MsgExecLegacyContent {
+                    content: Some(Any {
+                        type_url: GrantProviderPrivilegeProposal::TYPE_URL.to_string(),
+                        value: GrantProviderPrivilegeProposal {
+                            title: "grant provider privilege".to_string(),
+                            description: "grant provider privilege".to_string(),
+                            provider: "test-provider".to_string(),
+                            relayers: vec![signer.address()],
+                        }
+                        .encode_to_vec(),
+                    }),
+                    authority: GOV_MODULE_ADDRESS.to_string(),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        let now = app.get_block_time_seconds();
+        exchange
+            .instant_binary_options_market_launch(
+                v1beta1::MsgInstantBinaryOptionsMarketLaunch {
+                    sender: signer.address(),
+                    ticker: "INJ>10".to_string(),
+                    oracle_symbol: "INJ>10".to_string(),
+                    oracle_provider: "test-provider".to_string(),
+                    oracle_type: OracleType::Provider as i32,
+                    oracle_scale_factor: 0,
+                    maker_fee_rate: "1000000000000000".to_string(),
+                    taker_fee_rate: "1000000000000000".to_string(),
+                    expiration_timestamp: now + 3600,
+                    settlement_timestamp: now + 7200,
+                    admin: signer.address(),
+                    quote_denom: "usdt".to_string(),
+                    min_price_tick_size: "10000".to_string(),
+                    min_quantity_tick_size: "1".to_string(),
+                    min_notional: "1".to_string(),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        let market = exchange
+            .query_binary_options_markets(&v1beta1::QueryBinaryOptionsMarketsRequest {
+                status: "Active".to_string(),
+            })
+            .unwrap()
+            .markets
+            .pop()
+            .unwrap();
+        assert_eq!(market.ticker, "INJ>10");
+
+        // the admin postpones expiration and settlement by an hour
+        exchange
+            .admin_update_binary_options_market(
+                v1beta1::MsgAdminUpdateBinaryOptionsMarket {
+                    sender: signer.address(),
+                    market_id: market.market_id.clone(),
+                    settlement_price: "".to_string(),
+                    expiration_timestamp: now + 7200,
+                    settlement_timestamp: now + 10800,
+                    status: v1beta1::MarketStatus::Unspecified as i32,
+                },
+                &signer,
+            )
+            .unwrap();
+
+        let market = exchange
+            .query_binary_options_markets(&v1beta1::QueryBinaryOptionsMarketsRequest {
+                status: "Active".to_string(),
+            })
+            .unwrap()
+            .markets
+            .pop()
+            .unwrap();
+        assert_eq!(market.expiration_timestamp, now + 7200);
+        assert_eq!(market.settlement_timestamp, now + 10800);
+
+        let subaccount_id = default_subaccount_id(&signer);
+
+        let order_hash = exchange
+            .create_binary_options_limit_order(
+                v1beta1::MsgCreateBinaryOptionsLimitOrder {
+                    sender: signer.address(),
+                    order: Some(v1beta1::DerivativeOrder {
+                        market_id: market.market_id.clone(),
+                        order_info: Some(v1beta1::OrderInfo {
+                            subaccount_id: subaccount_id.clone(),
+                            fee_recipient: signer.address(),
+                            price: "500000000000000000".to_string(),
+                            quantity: "1000000000000000000".to_string(),
+                            cid: "".to_string(),
+                        }),
+                        order_type: 1i32,
+                        margin: "500000000000000000".to_string(),
+                        trigger_price: "".to_string(),
+                    }),
+                },
+                &signer,
+            )
+            .unwrap()
+            .data
+            .order_hash;
+
+        exchange
+            .cancel_binary_options_order(
+                v1beta1::MsgCancelBinaryOptionsOrder {
+                    sender: signer.address(),
+                    market_id: market.market_id.clone(),
+                    subaccount_id,
+                    order_hash,
+                    order_mask: 1i32,
+                    cid: "".to_string(),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        exchange
+            .settle_binary_options_market(&market.market_id, "1000000000000000000", &signer)
+            .unwrap();
+
+        let market = exchange
+            .query_binary_options_markets(&v1beta1::QueryBinaryOptionsMarketsRequest {
+                status: "".to_string(),
+            })
+            .unwrap()
+            .markets
+            .into_iter()
+            .find(|m| m.market_id == market.market_id)
+            .unwrap();
+        assert_eq!(market.status, v1beta1::MarketStatus::Demolished as i32);
+        assert_eq!(market.settlement_price, "1000000000000000000");
+    }
+
+    #[test]
+    fn exchange_transfers_and_rewards_opt_out() {
+        let app = InjectiveTestApp::new();
+        let accs = app
+            .init_accounts(&[Coin::new(1_000_000_000_000_000_000_000u128, "inj")], 2)
+            .unwrap();
+        let (sender, receiver) = (&accs[0], &accs[1]);
+
+        let exchange = Exchange::new(&app);
+
+        let source_subaccount_id = subaccount_id(sender, 1);
+        let destination_subaccount_id = subaccount_id(sender, 2);
+        let external_subaccount_id = subaccount_id(receiver, 1);
+
+        let inj = |amount: u128| {
+            Some(SDKCoin {
+                amount: amount.to_string(),
+                denom: "inj".to_string(),
+            })
+        };
+        let available_balance = |subaccount_id: &str| {
+            exchange
+                .query_subaccount_deposits(&v1beta1::QuerySubaccountDepositsRequest {
+                    subaccount_id: subaccount_id.to_string(),
+                    subaccount: None,
+                })
+                .unwrap()
+                .deposits["inj"]
+                .available_balance
+                .clone()
+        };
+
+        exchange
+            .deposit(
+                v1beta1::MsgDeposit {
+                    sender: sender.address(),
+                    subaccount_id: source_subaccount_id.clone(),
+                    amount: inj(100),
+                },
+                sender,
+            )
+            .unwrap();
+
+        exchange
+            .subaccount_transfer(
+                v1beta1::MsgSubaccountTransfer {
+                    sender: sender.address(),
+                    source_subaccount_id: source_subaccount_id.clone(),
+                    destination_subaccount_id: destination_subaccount_id.clone(),
+                    amount: inj(40),
+                },
+                sender,
+            )
+            .unwrap();
+
+        exchange
+            .external_transfer(
+                v1beta1::MsgExternalTransfer {
+                    sender: sender.address(),
+                    source_subaccount_id: source_subaccount_id.clone(),
+                    destination_subaccount_id: external_subaccount_id.clone(),
+                    amount: inj(10),
+                },
+                sender,
+            )
+            .unwrap();
+
+        assert_eq!(
+            available_balance(&source_subaccount_id),
+            "50000000000000000000"
+        );
+        assert_eq!(
+            available_balance(&destination_subaccount_id),
+            "40000000000000000000"
+        );
+        assert_eq!(
+            available_balance(&external_subaccount_id),
+            "10000000000000000000"
+        );
+
+        exchange
+            .rewards_opt_out(
+                v1beta1::MsgRewardsOptOut {
+                    sender: receiver.address(),
+                },
+                receiver,
+            )
+            .unwrap();
+
+        let res = exchange
+            .query_is_opted_out_of_rewards(&v1beta1::QueryIsOptedOutOfRewardsRequest {
+                account: receiver.address(),
+            })
+            .unwrap();
+        assert!(res.is_opted_out);
+    }
+
+    #[test]
+    fn derivative_position_margin_and_liquidation() {
+        let app = InjectiveTestApp::new();
+        let fixture = ExchangeFixture::builder(&app)
+            .denom("atom", 6)
+            .denom("usdt", 6)
+            .traders(3, 1_000_000_000_000)
+            .perpetual_market(
+                // 10 USDT in chain units
+                MarketSpec::new("atom", "usdt").with_oracle_price("10000000000000000000000000"),
+            )
+            .build()
+            .unwrap();
+        let (maker, taker, liquidator) = (
+            &fixture.traders[0],
+            &fixture.traders[1],
+            &fixture.traders[2],
+        );
+        let (maker_subaccount_id, taker_subaccount_id, liquidator_subaccount_id) = (
+            fixture.trader_subaccount_ids[0].clone(),
+            fixture.trader_subaccount_ids[1].clone(),
+            fixture.trader_subaccount_ids[2].clone(),
+        );
+        let market_id = fixture.perpetual_market_ids[0].clone();
+
+        let exchange = Exchange::new(&app);
+        let oracle = Oracle::new(&app);
+
+        // quote amounts, e.g. margins, are scaled like prices
+        let decimals = MarketDecimals::derivative(6);
+        let usdt = |amount: &str| decimals.price_to_chain(amount).unwrap();
+        let relay_price = |price: &str| {
+            oracle
+                .relay_price_feed(
+                    MsgRelayPriceFeedPrice {
+                        sender: fixture.owner.address(),
+                        base: vec!["atom".to_string()],
+                        quote: vec!["usdt".to_string()],
+                        price: vec![usdt(price)],
+                    },
+                    &fixture.owner,
+                )
+                .unwrap();
+        };
+        let position = |subaccount_id: &str| {
+            exchange
+                .query_subaccount_position_in_market(
+                    &v1beta1::QuerySubaccountPositionInMarketRequest {
+                        subaccount_id: subaccount_id.to_string(),
+                        market_id: market_id.clone(),
+                    },
+                )
+                .unwrap()
+                .state
+        };
+
+        // the taker goes long 1 ATOM at 10 USDT with 10x leverage against the maker
+        exchange
+            .seed_orderbook(&market_id, &[], &[PriceLevel::new("10", "1")], maker)
+            .unwrap();
+        exchange
+            .create_derivative_market_order(
+                v1beta1::MsgCreateDerivativeMarketOrder {
+                    sender: taker.address(),
+                    order: Some(v1beta1::DerivativeOrder {
+                        market_id: market_id.clone(),
+                        order_info: Some(v1beta1::OrderInfo {
+                            subaccount_id: taker_subaccount_id.clone(),
+                            fee_recipient: taker.address(),
+                            price: usdt("10"),
+                            quantity: decimals.quantity_to_chain("1").unwrap(),
+                            cid: "".to_string(),
+                        }),
+                        order_type: v1beta1::OrderType::Buy as i32,
+                        margin: usdt("1"),
+                        trigger_price: "".to_string(),
+                    }),
+                },
+                taker,
+            )
+            .unwrap();
+
+        let long = position(&taker_subaccount_id).unwrap();
+        assert!(long.is_long);
+        assert_eq!(long.margin, usdt("1"));
+
+        exchange
+            .increase_position_margin(
+                v1beta1::MsgIncreasePositionMargin {
+                    sender: taker.address(),
+                    source_subaccount_id: taker_subaccount_id.clone(),
+                    destination_subaccount_id: taker_subaccount_id.clone(),
+                    market_id: market_id.clone(),
+                    amount: usdt("1"),
+                },
+                taker,
+            )
+            .unwrap();
+        assert_eq!(position(&taker_subaccount_id).unwrap().margin, usdt("2"));
+
+        exchange
+            .decrease_position_margin(
+                v1beta1::MsgDecreasePositionMargin {
+                    sender: taker.address(),
+                    source_subaccount_id: taker_subaccount_id.clone(),
+                    destination_subaccount_id: taker_subaccount_id.clone(),
+                    market_id: market_id.clone(),
+                    amount: usdt("1"),
+                },
+                taker,
+            )
+            .unwrap();
+        assert_eq!(position(&taker_subaccount_id).unwrap().margin, usdt("1"));
+
+        // at 8.5 USDT the loss exceeds the margin, the liquidator takes over the position
+        relay_price("8.5");
+        exchange
+            .liquidate_position(
+                v1beta1::MsgLiquidatePosition {
+                    sender: liquidator.address(),
+                    subaccount_id: taker_subaccount_id.clone(),
+                    market_id: market_id.clone(),
+                    order: Some(v1beta1::DerivativeOrder {
+                        market_id: market_id.clone(),
+                        order_info: Some(v1beta1::OrderInfo {
+                            subaccount_id: liquidator_subaccount_id.clone(),
+                            fee_recipient: liquidator.address(),
+                            price: usdt("8.5"),
+                            quantity: decimals.quantity_to_chain("1").unwrap(),
+                            cid: "".to_string(),
+                        }),
+                        order_type: v1beta1::OrderType::Buy as i32,
+                        margin: usdt("8.5"),
+                        trigger_price: "".to_string(),
+                    }),
+                },
+                liquidator,
+            )
+            .unwrap();
+        assert_eq!(position(&taker_subaccount_id), None);
+        assert!(position(&liquidator_subaccount_id).unwrap().is_long);
+
+        // the loss of the short exceeds the insurance fund and no one is left to take it
+        // over, so the market can only be settled
+        relay_price("10000000000000");
+        exchange
+            .emergency_settle_market(
+                v1beta1::MsgEmergencySettleMarket {
+                    sender: liquidator.address(),
+                    subaccount_id: maker_subaccount_id.clone(),
+                    market_id: market_id.clone(),
+                },
+                liquidator,
+            )
+            .unwrap();
+
+        let paused_markets = exchange
+            .query_derivative_markets(&v1beta1::QueryDerivativeMarketsRequest {
+                status: "Paused".to_string(),
+                market_ids: vec![market_id.clone()],
+                with_mid_price_and_tob: false,
+            })
+            .unwrap()
+            .markets;
+        assert_eq!(paused_markets.len(), 1);
+        assert_eq!(position(&maker_subaccount_id), None);
+    }
+
+    #[test]
+    fn exchange_query_routes() {
+        let app = InjectiveTestApp::new();
+        let fixture = ExchangeFixture::builder(&app)
+            .denom("atom", 6)
+            .denom("usdt", 6)
+            .traders(1, 1_000_000_000)
+            .spot_market(MarketSpec::new("atom", "usdt"))
+            .perpetual_market(MarketSpec::new("atom", "usdt"))
+            .build()
+            .unwrap();
+        let exchange = Exchange::new(&app);
+
+        let account = fixture.traders[0].address();
+        let subaccount_id = fixture.trader_subaccount_ids[0].clone();
+        let spot_market_id = fixture.spot_market_ids[0].clone();
+        let perpetual_market_id = fixture.perpetual_market_ids[0].clone();
+
+        // markets
+        exchange
+            .query_spot_markets(&v1beta1::QuerySpotMarketsRequest::default())
+            .unwrap();
+        exchange
+            .query_derivative_markets(&v1beta1::QueryDerivativeMarketsRequest::default())
+            .unwrap();
+        exchange
+            .query_binary_options_markets(&v1beta1::QueryBinaryOptionsMarketsRequest::default())
+            .unwrap();
+        exchange
+            .query_full_spot_markets(&v1beta1::QueryFullSpotMarketsRequest::default())
+            .unwrap();
+        exchange
+            .query_full_spot_market(&v1beta1::QueryFullSpotMarketRequest {
+                market_id: spot_market_id.clone(),
+                with_mid_price_and_tob: true,
+            })
+            .unwrap();
+        exchange
+            .query_derivative_market_address(&v1beta1::QueryDerivativeMarketAddressRequest {
+                market_id: perpetual_market_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_perpetual_market_info(&v1beta1::QueryPerpetualMarketInfoRequest {
+                market_id: perpetual_market_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_perpetual_market_funding(&v1beta1::QueryPerpetualMarketFundingRequest {
+                market_id: perpetual_market_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_historical_trade_records(&v1beta1::QueryHistoricalTradeRecordsRequest {
+                market_id: spot_market_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_market_volatility(&v1beta1::QueryMarketVolatilityRequest {
+                market_id: spot_market_id.clone(),
+                trade_history_options: Some(v1beta1::TradeHistoryOptions::default()),
+            })
+            .unwrap();
+        exchange
+            .query_market_atomic_execution_fee_multiplier(
+                &v1beta1::QueryMarketAtomicExecutionFeeMultiplierRequest {
+                    market_id: spot_market_id.clone(),
+                },
+            )
+            .unwrap();
+
+        // module state, params and denoms
+        exchange
+            .query_exchange_module_state(&v1beta1::QueryModuleStateRequest {})
+            .unwrap();
+        let params = exchange
+            .query_exchange_params(&v1beta1::QueryExchangeParamsRequest {})
+            .unwrap()
+            .params
+            .unwrap();
+        assert!(!params
+            .spot_market_instant_listing_fee
+            .unwrap()
+            .amount
+            .is_empty());
+        exchange
+            .query_exchange_balances(&v1beta1::QueryExchangeBalancesRequest {})
+            .unwrap();
+        exchange
+            .query_balance_mismatches(&v1beta1::QueryBalanceMismatchesRequest::default())
+            .unwrap();
+        exchange
+            .query_balance_with_balance_holds(&v1beta1::QueryBalanceWithBalanceHoldsRequest {})
+            .unwrap();
+        exchange
+            .query_denom_decimal(&v1beta1::QueryDenomDecimalRequest {
+                denom: fixture.denom("usdt").to_string(),
+            })
+            .unwrap();
+        exchange
+            .query_denom_decimals(&v1beta1::QueryDenomDecimalsRequest { denoms: vec![] })
+            .unwrap();
+
+        // subaccounts and orders
+        exchange
+            .query_subaccount_deposit(&v1beta1::QuerySubaccountDepositRequest {
+                subaccount_id: subaccount_id.clone(),
+                denom: fixture.denom("usdt").to_string(),
+            })
+            .unwrap();
+        exchange
+            .query_subaccount_trade_nonce(&v1beta1::QuerySubaccountTradeNonceRequest {
+                subaccount_id: subaccount_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_subaccount_order_metadata(&v1beta1::QuerySubaccountOrderMetadataRequest {
+                subaccount_id: subaccount_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_subaccount_orders(&v1beta1::QuerySubaccountOrdersRequest {
+                subaccount_id: subaccount_id.clone(),
+                market_id: spot_market_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_account_address_spot_orders(&v1beta1::QueryAccountAddressSpotOrdersRequest {
+                market_id: spot_market_id.clone(),
+                account_address: account.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_spot_orders_by_hashes(&v1beta1::QuerySpotOrdersByHashesRequest {
+                market_id: spot_market_id.clone(),
+                subaccount_id: subaccount_id.clone(),
+                order_hashes: vec![],
+            })
+            .unwrap();
+        exchange
+            .query_trader_spot_transient_orders(&v1beta1::QueryTraderSpotOrdersRequest {
+                market_id: spot_market_id.clone(),
+                subaccount_id: subaccount_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_account_address_derivative_orders(
+                &v1beta1::QueryAccountAddressDerivativeOrdersRequest {
+                    market_id: perpetual_market_id.clone(),
+                    account_address: account.clone(),
+                },
+            )
+            .unwrap();
+        exchange
+            .query_derivative_orders_by_hashes(&v1beta1::QueryDerivativeOrdersByHashesRequest {
+                market_id: perpetual_market_id.clone(),
+                subaccount_id: subaccount_id.clone(),
+                order_hashes: vec![],
+            })
+            .unwrap();
+        exchange
+            .query_trader_derivative_transient_orders(
+                &v1beta1::QueryTraderDerivativeOrdersRequest {
+                    market_id: perpetual_market_id.clone(),
+                    subaccount_id: subaccount_id.clone(),
+                },
+            )
+            .unwrap();
+        exchange
+            .query_trader_derivative_conditional_orders(
+                &v1beta1::QueryTraderDerivativeConditionalOrdersRequest {
+                    subaccount_id: subaccount_id.clone(),
+                    market_id: perpetual_market_id.clone(),
+                },
+            )
+            .unwrap();
+
+        // volumes and rewards
+        exchange
+            .query_aggregate_volume(&v1beta1::QueryAggregateVolumeRequest {
+                account: account.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_aggregate_volumes(&v1beta1::QueryAggregateVolumesRequest {
+                accounts: vec![account.clone()],
+                market_ids: vec![spot_market_id.clone()],
+            })
+            .unwrap();
+        exchange
+            .query_aggregate_market_volume(&v1beta1::QueryAggregateMarketVolumeRequest {
+                market_id: spot_market_id.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_aggregate_market_volumes(&v1beta1::QueryAggregateMarketVolumesRequest {
+                market_ids: vec![spot_market_id.clone()],
+            })
+            .unwrap();
+        exchange
+            .query_is_opted_out_of_rewards(&v1beta1::QueryIsOptedOutOfRewardsRequest {
+                account: account.clone(),
+            })
+            .unwrap();
+        exchange
+            .query_opted_out_of_rewards_accounts(&v1beta1::QueryOptedOutOfRewardsAccountsRequest {})
+            .unwrap();
+        exchange
+            .query_trade_reward_points(&v1beta1::QueryTradeRewardPointsRequest {
+                accounts: vec![account.clone()],
+                pending_pool_timestamp: 0,
+            })
+            .unwrap();
+        exchange
+            .query_pending_trade_reward_points(&v1beta1::QueryTradeRewardPointsRequest {
+                accounts: vec![account.clone()],
+                pending_pool_timestamp: 0,
+            })
+            .unwrap();
+        exchange
+            .query_trade_reward_campaign(&v1beta1::QueryTradeRewardCampaignRequest {})
+            .unwrap();
+        exchange
+            .query_fee_discount_schedule(&v1beta1::QueryFeeDiscountScheduleRequest {})
+            .unwrap();
+        exchange
+            .query_fee_discount_tier_statistics(&v1beta1::QueryFeeDiscountTierStatisticsRequest {})
+            .unwrap();
+        exchange
+            .query_mito_vault_infos(&v1beta1::MitoVaultInfosRequest {})
+            .unwrap();
+
+        // no fee discount schedule or vault exists, so the account and vault address
+        // are left empty to fail on their validation
+        let err = exchange
+            .query_fee_discount_account_info(&v1beta1::QueryFeeDiscountAccountInfoRequest {
+                account: "".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::QueryError { .. }));
+        assert!(err
+            .to_string()
+            .contains("empty address string is not allowed"));
+        let err = exchange
+            .query_market_id_from_vault(&v1beta1::QueryMarketIdFromVaultRequest {
+                vault_address: "".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::QueryError { .. }));
+        assert!(err
+            .to_string()
+            .contains("empty address string is not allowed"));
+    }
+
+    #[test]
+    fn seed_orderbook_and_snapshot() {
+        let app = InjectiveTestApp::new();
+        let fixture = ExchangeFixture::builder(&app)
+            .denom("atom", 6)
+            .denom("usdt", 6)
+            .traders(1, 1_000_000_000_000)
+            .spot_market(MarketSpec::new("atom", "usdt"))
+            .perpetual_market(
+                // 10 USDT in chain units
+                MarketSpec::new("atom", "usdt").with_oracle_price("10000000000000000000000000"),
+            )
+            .build()
+            .unwrap();
+        let maker = &fixture.traders[0];
+
+        let exchange = Exchange::new(&app);
+
+        assert_eq!(
+            exchange
+                .query_market_decimals(&fixture.spot_market_ids[0])
+                .unwrap(),
+            MarketDecimals::spot(6, 6)
+        );
+        assert_eq!(
+            exchange
+                .query_market_decimals(&fixture.perpetual_market_ids[0])
+                .unwrap(),
+            MarketDecimals::derivative(6)
+        );
+
+        let bids = [
+            PriceLevel::new("10", "1"),
+            PriceLevel::new("10", "2"),
+            PriceLevel::new("9.5", "1"),
+        ];
+        let asks = [PriceLevel::new("11", "0.5")];
+        let expected = OrderbookSnapshot {
+            buys: vec![PriceLevel::new("10", "3"), PriceLevel::new("9.5", "1")],
+            sells: vec![PriceLevel::new("11", "0.5")],
+        };
+
+        for market_id in [
+            &fixture.spot_market_ids[0],
+            &fixture.perpetual_market_ids[0],
+        ] {
+            let res = exchange
+                .seed_orderbook(market_id, &bids, &asks, maker)
+                .unwrap();
+            assert_eq!(
+                res.spot_order_hashes.len() + res.derivative_order_hashes.len(),
+                4
+            );
+
+            assert_eq!(exchange.orderbook_snapshot(market_id).unwrap(), expected);
+        }
+
+        assert!(PriceLevel::try_new("ten", "1").is_err());
+        assert!(PriceLevel::try_new("10", "-1").is_err());
+
+        let err = exchange
+            .query_market_decimals(&format!("0x{}", "00".repeat(32)))
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::QueryError { .. }));
+    }
+
+    #[test]
+    fn denom_decimals_require_metadata() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        let exchange = Exchange::new(&app);
+        let tokenfactory = TokenFactory::new(&app);
+
+        let atom = tokenfactory
+            .create_denom_with_metadata("atom", 6, "ATOM", &signer)
+            .unwrap();
+        assert_eq!(exchange.denom_decimals(&atom).unwrap(), 6);
+
+        // a zero decimals denom can't be told apart from one whose decimals were never set
+        let points = tokenfactory
+            .create_denom_with_metadata("points", 0, "PTS", &signer)
+            .unwrap();
+        let err = exchange.denom_decimals(&points).unwrap_err();
+        assert!(matches!(err, RunnerError::GenericError(_)));
+
+        let err = exchange.denom_decimals("ibc/unknown").unwrap_err();
+        assert!(matches!(err, RunnerError::QueryError { .. }));
+    }
 }
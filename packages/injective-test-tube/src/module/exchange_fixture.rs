@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::Coin;
+use injective_std::shim::Any;
+use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+use injective_std::types::cosmos::gov::v1::MsgExecLegacyContent;
+use injective_std::types::injective::exchange::v1beta1::{
+    MsgInstantPerpetualMarketLaunch, MsgInstantSpotMarketLaunch, QueryDerivativeMarketsRequest,
+    QuerySpotMarketsRequest,
+};
+use injective_std::types::injective::insurance::v1beta1::MsgCreateInsuranceFund;
+use injective_std::types::injective::oracle::v1beta1::{
+    GrantPriceFeederPrivilegeProposal, MsgRelayPriceFeedPrice, OracleType,
+};
+use prost::Message;
+use test_tube_inj::account::{Account, SigningAccount};
+use test_tube_inj::module::Module;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::RunnerResult;
+
+use crate::{
+    default_subaccount_id, Exchange, GovWithAppAccess, InjectiveTestApp, Insurance, Oracle,
+    TokenFactory, GOV_MODULE_ADDRESS,
+};
+
+// covers denom creation, market listing and proposal deposit fees
+const OWNER_INJ_AMOUNT: u128 = 10_000_000_000_000_000_000_000_000;
+// covers the fees of the transactions sent by a trader
+const TRADER_INJ_AMOUNT: u128 = 1_000_000_000_000_000_000_000;
+const INSURANCE_FUND_DEPOSIT: u128 = 1_000_000_000_000_000_000;
+
+const DEFAULT_MIN_PRICE_TICK_SIZE: &str = "10000";
+const DEFAULT_MIN_QUANTITY_TICK_SIZE: &str = "100000";
+const DEFAULT_MIN_NOTIONAL: &str = "1";
+const DEFAULT_ORACLE_PRICE: &str = "1000000000000000000";
+const MAKER_FEE_RATE: &str = "1000000000000000";
+const TAKER_FEE_RATE: &str = "2000000000000000";
+const INITIAL_MARGIN_RATIO: &str = "50000000000000000";
+const MAINTENANCE_MARGIN_RATIO: &str = "20000000000000000";
+
+/// Market to be launched by [`ExchangeFixtureBuilder`].
+///
+/// `base` and `quote` refer to subdenoms registered with [`ExchangeFixtureBuilder::denom`],
+/// so that traders and insurance funds can be funded with them. Tick sizes and the oracle
+/// price are chain representations, i.e. scaled by 10^18.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketSpec {
+    pub base: String,
+    pub quote: String,
+    pub min_price_tick_size: String,
+    pub min_quantity_tick_size: String,
+    pub min_notional: String,
+    /// Price relayed to the price feed oracle, only used by perpetual markets.
+    pub oracle_price: String,
+}
+
+impl MarketSpec {
+    pub fn new(base: &str, quote: &str) -> Self {
+        Self {
+            base: base.to_string(),
+            quote: quote.to_string(),
+            min_price_tick_size: DEFAULT_MIN_PRICE_TICK_SIZE.to_string(),
+            min_quantity_tick_size: DEFAULT_MIN_QUANTITY_TICK_SIZE.to_string(),
+            min_notional: DEFAULT_MIN_NOTIONAL.to_string(),
+            oracle_price: DEFAULT_ORACLE_PRICE.to_string(),
+        }
+    }
+
+    pub fn with_tick_sizes(
+        mut self,
+        min_price_tick_size: &str,
+        min_quantity_tick_size: &str,
+    ) -> Self {
+        self.min_price_tick_size = min_price_tick_size.to_string();
+        self.min_quantity_tick_size = min_quantity_tick_size.to_string();
+        self
+    }
+
+    pub fn with_min_notional(mut self, min_notional: &str) -> Self {
+        self.min_notional = min_notional.to_string();
+        self
+    }
+
+    pub fn with_oracle_price(mut self, oracle_price: &str) -> Self {
+        self.oracle_price = oracle_price.to_string();
+        self
+    }
+
+    fn ticker(&self) -> String {
+        format!("{}/{}", self.base.to_uppercase(), self.quote.to_uppercase())
+    }
+}
+
+/// Exchange state set up by [`ExchangeFixtureBuilder::build`].
+pub struct ExchangeFixture {
+    /// Creator of the denoms and markets, as well as the price feed relayer.
+    pub owner: SigningAccount,
+    /// Full token factory denoms by subdenom.
+    pub denoms: HashMap<String, String>,
+    pub traders: Vec<SigningAccount>,
+    /// Default subaccount ids of `traders`, in the same order.
+    pub trader_subaccount_ids: Vec<String>,
+    /// Ids of the spot markets, in the order they were added to the builder.
+    pub spot_market_ids: Vec<String>,
+    /// Ids of the perpetual markets, in the order they were added to the builder.
+    pub perpetual_market_ids: Vec<String>,
+}
+
+impl ExchangeFixture {
+    pub fn builder(app: &InjectiveTestApp) -> ExchangeFixtureBuilder<'_> {
+        ExchangeFixtureBuilder::new(app)
+    }
+
+    /// Full denom of `subdenom`, panics if it wasn't created by the fixture.
+    pub fn denom(&self, subdenom: &str) -> &str {
+        &self.denoms[subdenom]
+    }
+}
+
+/// Builds an [`ExchangeFixture`], which replaces the denom, oracle and market
+/// setup boilerplate of exchange tests.
+pub struct ExchangeFixtureBuilder<'a> {
+    app: &'a InjectiveTestApp,
+    denoms: Vec<(String, u32)>,
+    trader_count: u64,
+    trader_funds: u128,
+    spot_markets: Vec<MarketSpec>,
+    perpetual_markets: Vec<MarketSpec>,
+}
+
+impl<'a> ExchangeFixtureBuilder<'a> {
+    pub fn new(app: &'a InjectiveTestApp) -> Self {
+        Self {
+            app,
+            denoms: vec![],
+            trader_count: 0,
+            trader_funds: 0,
+            spot_markets: vec![],
+            perpetual_markets: vec![],
+        }
+    }
+
+    /// Create `factory/{owner}/{subdenom}` with `decimals` decimals.
+    pub fn denom(mut self, subdenom: &str, decimals: u32) -> Self {
+        self.denoms.push((subdenom.to_string(), decimals));
+        self
+    }
+
+    /// Create `count` traders, each funded with `funds` of every created denom.
+    pub fn traders(mut self, count: u64, funds: u128) -> Self {
+        self.trader_count = count;
+        self.trader_funds = funds;
+        self
+    }
+
+    pub fn spot_market(mut self, spec: MarketSpec) -> Self {
+        self.spot_markets.push(spec);
+        self
+    }
+
+    /// Add a perpetual market using a price feed oracle for its base and quote,
+    /// with the owner as relayer.
+    pub fn perpetual_market(mut self, spec: MarketSpec) -> Self {
+        self.perpetual_markets.push(spec);
+        self
+    }
+
+    pub fn build(self) -> RunnerResult<ExchangeFixture> {
+        // fail before setting anything up rather than halfway through for lack of funds
+        for spec in self.spot_markets.iter().chain(&self.perpetual_markets) {
+            for denom in [&spec.base, &spec.quote] {
+                if !self.denoms.iter().any(|(subdenom, _)| subdenom == denom) {
+                    return Err(RunnerError::GenericError(format!(
+                        "market {} uses {}, which isn't a fixture denom",
+                        spec.ticker(),
+                        denom
+                    )));
+                }
+            }
+        }
+
+        let app = self.app;
+        let exchange = Exchange::new(app);
+        let tokenfactory = TokenFactory::new(app);
+
+        let owner = app.init_account(&[Coin::new(OWNER_INJ_AMOUNT, "inj")])?;
+
+        let mut denoms = HashMap::new();
+        for (subdenom, decimals) in &self.denoms {
+            let denom = tokenfactory.create_denom_with_metadata(
+                subdenom,
+                *decimals,
+                &subdenom.to_uppercase(),
+                &owner,
+            )?;
+            denoms.insert(subdenom.clone(), denom);
+        }
+
+        let traders = if self.trader_count > 0 {
+            app.init_accounts(&[Coin::new(TRADER_INJ_AMOUNT, "inj")], self.trader_count)?
+        } else {
+            vec![]
+        };
+        for trader in &traders {
+            for denom in denoms.values() {
+                tokenfactory.mint_to(
+                    Coin::new(self.trader_funds, denom),
+                    &trader.address(),
+                    &owner,
+                )?;
+            }
+        }
+        let trader_subaccount_ids = traders.iter().map(default_subaccount_id).collect();
+
+        let mut spot_market_ids = vec![];
+        for spec in &self.spot_markets {
+            let ticker = spec.ticker();
+            exchange.instant_spot_market_launch(
+                MsgInstantSpotMarketLaunch {
+                    sender: owner.address(),
+                    ticker: ticker.clone(),
+                    base_denom: denoms[&spec.base].clone(),
+                    quote_denom: denoms[&spec.quote].clone(),
+                    min_price_tick_size: spec.min_price_tick_size.clone(),
+                    min_quantity_tick_size: spec.min_quantity_tick_size.clone(),
+                    min_notional: spec.min_notional.clone(),
+                },
+                &owner,
+            )?;
+
+            let market_id = exchange
+                .query_spot_markets(&QuerySpotMarketsRequest {
+                    status: "".to_string(),
+                    market_ids: vec![],
+                })?
+                .markets
+                .into_iter()
+                .find(|market| market.ticker == ticker)
+                .map(|market| market.market_id)
+                .ok_or_else(|| market_not_found(&ticker))?;
+            spot_market_ids.push(market_id);
+        }
+
+        let mut perpetual_market_ids = vec![];
+        for spec in &self.perpetual_markets {
+            let ticker = format!("{} PERP", spec.ticker());
+            let quote_denom = denoms[&spec.quote].clone();
+
+            self.setup_price_feed(&owner, spec)?;
+
+            tokenfactory.mint_to(
+                Coin::new(INSURANCE_FUND_DEPOSIT, &quote_denom),
+                &owner.address(),
+                &owner,
+            )?;
+            Insurance::new(app).create_insurance_fund(
+                MsgCreateInsuranceFund {
+                    sender: owner.address(),
+                    ticker: ticker.clone(),
+                    quote_denom: quote_denom.clone(),
+                    oracle_base: spec.base.clone(),
+                    oracle_quote: spec.quote.clone(),
+                    oracle_type: OracleType::PriceFeed as i32,
+                    expiry: -1i64,
+                    initial_deposit: Some(BaseCoin {
+                        amount: INSURANCE_FUND_DEPOSIT.to_string(),
+                        denom: quote_denom.clone(),
+                    }),
+                },
+                &owner,
+            )?;
+
+            exchange.instant_perpetual_market_launch(
+                MsgInstantPerpetualMarketLaunch {
+                    sender: owner.address(),
+                    ticker: ticker.clone(),
+                    quote_denom,
+                    oracle_base: spec.base.clone(),
+                    oracle_quote: spec.quote.clone(),
+                    oracle_scale_factor: 0,
+                    oracle_type: OracleType::PriceFeed as i32,
+                    maker_fee_rate: MAKER_FEE_RATE.to_string(),
+                    taker_fee_rate: TAKER_FEE_RATE.to_string(),
+                    initial_margin_ratio: INITIAL_MARGIN_RATIO.to_string(),
+                    maintenance_margin_ratio: MAINTENANCE_MARGIN_RATIO.to_string(),
+                    min_price_tick_size: spec.min_price_tick_size.clone(),
+                    min_quantity_tick_size: spec.min_quantity_tick_size.clone(),
+                    min_notional: spec.min_notional.clone(),
+                },
+                &owner,
+            )?;
+
+            let market_id = exchange
+                .query_derivative_markets(&QueryDerivativeMarketsRequest {
+                    status: "".to_string(),
+                    market_ids: vec![],
+                    with_mid_price_and_tob: false,
+                })?
+                .markets
+                .into_iter()
+                .filter_map(|market| market.market)
+                .find(|market| market.ticker == ticker)
+                .map(|market| market.market_id)
+                .ok_or_else(|| market_not_found(&ticker))?;
+            perpetual_market_ids.push(market_id);
+        }
+
+        Ok(ExchangeFixture {
+            owner,
+            denoms,
+            traders,
+            trader_subaccount_ids,
+            spot_market_ids,
+            perpetual_market_ids,
+        })
+    }
+
+    /// Grant the owner the price feeder privilege for the market's base and quote
+    /// through governance, and relay the initial oracle price.
+    fn setup_price_feed(&self, owner: &SigningAccount, spec: &MarketSpec) -> RunnerResult<()> {
+        let proposal = GrantPriceFeederPrivilegeProposal {
+            title: format!("Grant {} price feeder privilege", spec.ticker()),
+            description: format!("Grant {} price feeder privilege", spec.ticker()),
+            base: spec.base.clone(),
+            quote: spec.quote.clone(),
+            relayers: vec![owner.address()],
+        };
+
+        GovWithAppAccess::new(self.app).propose_and_execute(
+            MsgExecLegacyContent::TYPE_URL.to_string(),
+            MsgExecLegacyContent {
+                content: Some(Any {
+                    type_url: GrantPriceFeederPrivilegeProposal::TYPE_URL.to_string(),
+                    value: proposal.encode_to_vec(),
+                }),
+                authority: GOV_MODULE_ADDRESS.to_string(),
+            },
+            owner,
+        )?;
+
+        Oracle::new(self.app).relay_price_feed(
+            MsgRelayPriceFeedPrice {
+                sender: owner.address(),
+                base: vec![spec.base.clone()],
+                quote: vec![spec.quote.clone()],
+                price: vec![spec.oracle_price.clone()],
+            },
+            owner,
+        )?;
+
+        Ok(())
+    }
+}
+
+fn market_not_found(ticker: &str) -> RunnerError {
+    RunnerError::QueryError {
+        msg: format!("market {} not found after launch", ticker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use injective_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use injective_std::types::injective::exchange::v1beta1::{
+        QueryDerivativeMarketRequest, QuerySpotMarketRequest,
+    };
+
+    use crate::{
+        Account, Bank, Exchange, ExchangeFixture, InjectiveTestApp, MarketSpec, Module, RunnerError,
+    };
+
+    #[test]
+    fn exchange_fixture_integration() {
+        let app = InjectiveTestApp::new();
+
+        let fixture = ExchangeFixture::builder(&app)
+            .denom("atom", 6)
+            .denom("usdt", 6)
+            .traders(2, 1_000_000_000_000)
+            .spot_market(MarketSpec::new("atom", "usdt"))
+            .perpetual_market(
+                MarketSpec::new("atom", "usdt").with_oracle_price("10000000000000000000"),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(fixture.traders.len(), 2);
+        assert_eq!(fixture.trader_subaccount_ids.len(), 2);
+        assert_eq!(fixture.spot_market_ids.len(), 1);
+        assert_eq!(fixture.perpetual_market_ids.len(), 1);
+
+        let balance = Bank::new(&app)
+            .query_balance(&QueryBalanceRequest {
+                address: fixture.traders[1].address(),
+                denom: fixture.denom("usdt").to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000000000000");
+
+        let exchange = Exchange::new(&app);
+
+        let spot_market = exchange
+            .query_spot_market(&QuerySpotMarketRequest {
+                market_id: fixture.spot_market_ids[0].clone(),
+            })
+            .unwrap()
+            .market
+            .unwrap();
+        assert_eq!(spot_market.ticker, "ATOM/USDT");
+        assert_eq!(spot_market.base_denom, fixture.denom("atom"));
+
+        let perpetual_market = exchange
+            .query_derivative_market(&QueryDerivativeMarketRequest {
+                market_id: fixture.perpetual_market_ids[0].clone(),
+            })
+            .unwrap()
+            .market
+            .unwrap();
+        assert_eq!(perpetual_market.mark_price, "10000000000000000000");
+        assert_eq!(
+            perpetual_market.market.unwrap().quote_denom,
+            fixture.denom("usdt")
+        );
+
+        // markets can only be quoted in fixture denoms, which traders are funded with
+        let err = ExchangeFixture::builder(&app)
+            .denom("atom", 6)
+            .perpetual_market(MarketSpec::new("atom", "inj"))
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err, RunnerError::GenericError(_)));
+    }
+}
@@ -1,12 +1,27 @@
+use injective_std::shim::Any;
+use injective_std::types::cosmos::bank::v1beta1::MsgSend;
+use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
 use injective_std::types::cosmos::gov::v1::{
-    MsgSubmitProposal, MsgSubmitProposalResponse, MsgVote, MsgVoteResponse, QueryProposalRequest,
-    QueryProposalResponse,
+    MsgSubmitProposal, MsgSubmitProposalResponse, MsgVote, MsgVoteResponse, ProposalStatus,
+    QueryParamsRequest, QueryParamsResponse, QueryProposalRequest, QueryProposalResponse,
+    VoteOption,
 };
 use injective_std::types::cosmos::gov::v1beta1;
+use test_tube_inj::account::{Account, SigningAccount};
 use test_tube_inj::module::Module;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::RunnerExecuteResult;
 use test_tube_inj::runner::Runner;
 use test_tube_inj::{fn_execute, fn_query};
 
+use crate::{Bank, InjectiveTestApp};
+
+/// Address of the gov module account, the authority of governance gated messages.
+pub const GOV_MODULE_ADDRESS: &str = "inj10d07y265gmmuvt4z0w9aw880jnsr700jstypyt";
+
+// funds sent to the validator so that it can pay for its vote
+const VALIDATOR_VOTE_FEE_AMOUNT: u128 = 1_000_000_000_000_000_000;
+
 pub struct Gov<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -35,6 +50,179 @@ where
     }
 
     fn_query! {
-        pub query_proposal ["/cosmos.gov.v1beta1.Query/Proposal"]: QueryProposalRequest => QueryProposalResponse
+        pub query_proposal ["/cosmos.gov.v1.Query/Proposal"]: QueryProposalRequest => QueryProposalResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmos.gov.v1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+}
+
+/// Extension of [`Gov`] which can vote with the validator and move the chain
+/// past the voting period, so that proposals can be executed in a single call.
+pub struct GovWithAppAccess<'a> {
+    gov: Gov<'a, InjectiveTestApp>,
+    app: &'a InjectiveTestApp,
+}
+
+impl<'a> GovWithAppAccess<'a> {
+    pub fn new(app: &'a InjectiveTestApp) -> Self {
+        Self {
+            gov: Gov::new(app),
+            app,
+        }
+    }
+
+    /// Submit a proposal executing `msg` as the gov module, vote for it with the
+    /// validator and advance the block time past the voting period.
+    ///
+    /// `signer` pays for the minimum deposit as well as for the validator vote fees.
+    pub fn propose_and_execute<M: prost::Message>(
+        &self,
+        msg_type_url: String,
+        msg: M,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgSubmitProposalResponse> {
+        let params = self
+            .gov
+            .query_params(&QueryParamsRequest {
+                params_type: "".to_string(),
+            })?
+            .params
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: "gov params not found".to_string(),
+            })?;
+
+        let res = self.gov.submit_proposal(
+            MsgSubmitProposal {
+                messages: vec![Any {
+                    type_url: msg_type_url.clone(),
+                    value: msg.encode_to_vec(),
+                }],
+                initial_deposit: params.min_deposit,
+                proposer: signer.address(),
+                metadata: "".to_string(),
+                title: format!("Execute {}", msg_type_url),
+                summary: format!("Execute {} as the gov module", msg_type_url),
+                expedited: false,
+            },
+            signer,
+        )?;
+        let proposal_id = res.data.proposal_id;
+
+        let validator = self
+            .app
+            .get_first_validator_signing_account("inj".to_string(), 1.2f64)?;
+
+        // fund the validator account
+        Bank::new(self.app).send(
+            MsgSend {
+                from_address: signer.address(),
+                to_address: validator.address(),
+                amount: vec![BaseCoin {
+                    amount: VALIDATOR_VOTE_FEE_AMOUNT.to_string(),
+                    denom: "inj".to_string(),
+                }],
+            },
+            signer,
+        )?;
+
+        self.gov.vote(
+            MsgVote {
+                proposal_id,
+                voter: validator.address(),
+                option: VoteOption::Yes.into(),
+                metadata: "".to_string(),
+            },
+            &validator,
+        )?;
+
+        let voting_period = params
+            .voting_period
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: "gov voting period not found".to_string(),
+            })?
+            .seconds;
+
+        // NOTE: increase the block time in order to move past the voting period
+        self.app.increase_time(voting_period as u64 + 1);
+
+        let proposal = self
+            .gov
+            .query_proposal(&QueryProposalRequest { proposal_id })?
+            .proposal
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("proposal {} not found", proposal_id),
+            })?;
+
+        if proposal.status != i32::from(ProposalStatus::Passed) {
+            return Err(RunnerError::execute_error(format!(
+                "proposal {} did not pass: {}",
+                proposal_id, proposal.failed_reason
+            )));
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use injective_std::types::cosmos::bank::v1beta1::{
+        MsgSetSendEnabled, QuerySendEnabledRequest, SendEnabled,
+    };
+    use injective_std::types::cosmos::gov::v1::{ProposalStatus, QueryProposalRequest};
+
+    use crate::{
+        Account, Bank, Gov, GovWithAppAccess, InjectiveTestApp, Module, GOV_MODULE_ADDRESS,
+    };
+
+    #[test]
+    fn propose_and_execute_and_query_proposal() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+
+        let proposal_id = GovWithAppAccess::new(&app)
+            .propose_and_execute(
+                MsgSetSendEnabled::TYPE_URL.to_string(),
+                MsgSetSendEnabled {
+                    authority: GOV_MODULE_ADDRESS.to_string(),
+                    send_enabled: vec![SendEnabled {
+                        denom: "usdt".to_string(),
+                        enabled: false,
+                    }],
+                    use_default_for: vec![],
+                },
+                &signer,
+            )
+            .unwrap()
+            .data
+            .proposal_id;
+
+        let proposal = Gov::new(&app)
+            .query_proposal(&QueryProposalRequest { proposal_id })
+            .unwrap()
+            .proposal
+            .unwrap();
+        assert_eq!(proposal.id, proposal_id);
+        assert_eq!(proposal.status, i32::from(ProposalStatus::Passed));
+
+        let send_enabled = Bank::new(&app)
+            .query_send_enabled(&QuerySendEnabledRequest {
+                denoms: vec!["usdt".to_string()],
+                pagination: None,
+            })
+            .unwrap()
+            .send_enabled;
+        assert_eq!(
+            send_enabled,
+            vec![SendEnabled {
+                denom: "usdt".to_string(),
+                enabled: false,
+            }]
+        );
     }
 }
@@ -1,11 +1,19 @@
 use injective_std::types::injective::insurance::v1beta1::{
-    MsgCreateInsuranceFund, MsgCreateInsuranceFundResponse, QueryInsuranceFundRequest,
-    QueryInsuranceFundResponse, QueryModuleStateRequest, QueryModuleStateResponse,
+    MsgCreateInsuranceFund, MsgCreateInsuranceFundResponse, MsgRequestRedemption,
+    MsgRequestRedemptionResponse, MsgUnderwrite, MsgUnderwriteResponse,
+    QueryEstimatedRedemptionsRequest, QueryEstimatedRedemptionsResponse, QueryInsuranceFundRequest,
+    QueryInsuranceFundResponse, QueryInsuranceFundsRequest, QueryInsuranceFundsResponse,
+    QueryInsuranceParamsRequest, QueryInsuranceParamsResponse, QueryModuleStateRequest,
+    QueryModuleStateResponse, QueryPendingRedemptionsRequest, QueryPendingRedemptionsResponse,
 };
 use test_tube_inj::module::Module;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::RunnerResult;
 use test_tube_inj::runner::Runner;
 use test_tube_inj::{fn_execute, fn_query};
 
+use crate::InjectiveTestApp;
+
 pub struct Insurance<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -24,6 +32,14 @@ where
         pub create_insurance_fund: MsgCreateInsuranceFund => MsgCreateInsuranceFundResponse
     }
 
+    fn_execute! {
+        pub underwrite: MsgUnderwrite => MsgUnderwriteResponse
+    }
+
+    fn_execute! {
+        pub request_redemption: MsgRequestRedemption => MsgRequestRedemptionResponse
+    }
+
     fn_query! {
         pub query_module_state ["/injective.insurance.v1beta1.Query/InsuranceModuleState"]: QueryModuleStateRequest => QueryModuleStateResponse
     }
@@ -31,18 +47,61 @@ where
     fn_query! {
         pub query_insurance_fund ["/injective.insurance.v1beta1.Query/InsuranceFund"]: QueryInsuranceFundRequest => QueryInsuranceFundResponse
     }
+
+    fn_query! {
+        pub query_insurance_funds ["/injective.insurance.v1beta1.Query/InsuranceFunds"]: QueryInsuranceFundsRequest => QueryInsuranceFundsResponse
+    }
+
+    fn_query! {
+        pub query_insurance_params ["/injective.insurance.v1beta1.Query/InsuranceParams"]: QueryInsuranceParamsRequest => QueryInsuranceParamsResponse
+    }
+
+    fn_query! {
+        pub query_estimated_redemptions ["/injective.insurance.v1beta1.Query/EstimatedRedemptions"]: QueryEstimatedRedemptionsRequest => QueryEstimatedRedemptionsResponse
+    }
+
+    fn_query! {
+        pub query_pending_redemptions ["/injective.insurance.v1beta1.Query/PendingRedemptions"]: QueryPendingRedemptionsRequest => QueryPendingRedemptionsResponse
+    }
+}
+
+impl<'a> Insurance<'a, InjectiveTestApp> {
+    /// Advance the block time past the redemption notice period of the insurance fund
+    /// of `market_id`, so that redemptions requested before this call get processed.
+    pub fn advance_past_redemption_notice_period(&self, market_id: &str) -> RunnerResult<()> {
+        let fund = self
+            .query_insurance_fund(&QueryInsuranceFundRequest {
+                market_id: market_id.to_string(),
+            })?
+            .fund
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("insurance fund for market {} not found", market_id),
+            })?;
+
+        let notice_period = fund
+            .redemption_notice_period_duration
+            .map(|d| d.seconds)
+            .unwrap_or_default();
+
+        self.runner.increase_time(notice_period as u64 + 1);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Account, InjectiveTestApp, Insurance, Module};
+    use crate::{Account, Bank, InjectiveTestApp, Insurance, Module};
     use cosmwasm_std::Coin;
     use injective_std::{
         shim::Duration,
         types::{
+            cosmos::bank::v1beta1::QueryBalanceRequest,
             cosmos::base::v1beta1::Coin as TubeCoin,
             injective::insurance::v1beta1::{
-                InsuranceFund, MsgCreateInsuranceFund, QueryInsuranceFundRequest,
+                InsuranceFund, MsgCreateInsuranceFund, MsgRequestRedemption, MsgUnderwrite,
+                QueryEstimatedRedemptionsRequest, QueryInsuranceFundRequest,
+                QueryInsuranceFundsRequest, QueryPendingRedemptionsRequest,
             },
         },
     };
@@ -109,4 +168,143 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn insurance_redemption_lifecycle() {
+        let app = InjectiveTestApp::new();
+
+        let insurance = Insurance::new(&app);
+        let bank = Bank::new(&app);
+
+        let signer = app
+            .init_account(&[
+                Coin::new(100_000_000_000_000_000_000_000u128, "inj"),
+                Coin::new(100_000_000_000_000_000_000u128, "usdt"),
+            ])
+            .unwrap();
+        let underwriter = app
+            .init_account(&[
+                Coin::new(100_000_000_000_000_000_000_000u128, "inj"),
+                Coin::new(100_000_000_000_000_000_000u128, "usdt"),
+            ])
+            .unwrap();
+
+        let market_id = "0xc04ba8ebc86a97c57e4385ad264183a156c3afaffc0e4c398cc77120e2b3bab9";
+
+        insurance
+            .create_insurance_fund(
+                MsgCreateInsuranceFund {
+                    sender: signer.address(),
+                    ticker: "INJ/USDT".to_string(),
+                    quote_denom: "usdt".to_string(),
+                    oracle_base: "inj".to_string(),
+                    oracle_quote: "usdt".to_string(),
+                    oracle_type: 2i32,
+                    expiry: -1i64,
+                    initial_deposit: Some(TubeCoin {
+                        amount: "100000000000000000000".to_string(),
+                        denom: "usdt".to_string(),
+                    }),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        let funds = insurance
+            .query_insurance_funds(&QueryInsuranceFundsRequest {})
+            .unwrap()
+            .funds;
+        assert_eq!(funds.len(), 1);
+
+        insurance
+            .underwrite(
+                MsgUnderwrite {
+                    sender: underwriter.address(),
+                    market_id: market_id.to_string(),
+                    deposit: Some(TubeCoin {
+                        amount: "100000000000000000000".to_string(),
+                        denom: "usdt".to_string(),
+                    }),
+                },
+                &underwriter,
+            )
+            .unwrap();
+
+        let fund = insurance
+            .query_insurance_fund(&QueryInsuranceFundRequest {
+                market_id: market_id.to_string(),
+            })
+            .unwrap()
+            .fund
+            .unwrap();
+        assert_eq!(fund.balance, "200000000000000000000");
+        assert_eq!(fund.total_share, "2000000000000000000");
+
+        let estimated = insurance
+            .query_estimated_redemptions(&QueryEstimatedRedemptionsRequest {
+                market_id: market_id.to_string(),
+                address: underwriter.address(),
+            })
+            .unwrap()
+            .amount;
+        assert_eq!(
+            estimated,
+            vec![TubeCoin {
+                amount: "100000000000000000000".to_string(),
+                denom: "usdt".to_string(),
+            }]
+        );
+
+        insurance
+            .request_redemption(
+                MsgRequestRedemption {
+                    sender: underwriter.address(),
+                    market_id: market_id.to_string(),
+                    amount: Some(TubeCoin {
+                        amount: "1000000000000000000".to_string(),
+                        denom: fund.insurance_pool_token_denom.clone(),
+                    }),
+                },
+                &underwriter,
+            )
+            .unwrap();
+
+        let pending = insurance
+            .query_pending_redemptions(&QueryPendingRedemptionsRequest {
+                market_id: market_id.to_string(),
+                address: underwriter.address(),
+            })
+            .unwrap()
+            .amount;
+        assert_eq!(
+            pending,
+            vec![TubeCoin {
+                amount: "100000000000000000000".to_string(),
+                denom: "usdt".to_string(),
+            }]
+        );
+
+        insurance
+            .advance_past_redemption_notice_period(market_id)
+            .unwrap();
+
+        let pending = insurance
+            .query_pending_redemptions(&QueryPendingRedemptionsRequest {
+                market_id: market_id.to_string(),
+                address: underwriter.address(),
+            })
+            .unwrap()
+            .amount;
+        assert!(pending.is_empty());
+
+        let balance = bank
+            .query_balance(&QueryBalanceRequest {
+                address: underwriter.address(),
+                denom: "usdt".to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "100000000000000000000");
+    }
 }
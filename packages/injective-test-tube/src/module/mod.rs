@@ -2,11 +2,13 @@ mod auction;
 mod authz;
 mod bank;
 mod exchange;
+mod exchange_fixture;
 mod gov;
 mod insurance;
 mod oracle;
 mod staking;
 mod tokenfactory;
+mod vesting;
 mod wasm;
 mod wasmx;
 
@@ -14,13 +16,22 @@ pub use test_tube_inj::macros;
 pub use test_tube_inj::module::Module;
 
 pub use auction::Auction;
-pub use authz::Authz;
+pub use authz::{
+    batch_cancel_derivative_orders_authz, batch_cancel_spot_orders_authz,
+    batch_create_derivative_limit_orders_authz, batch_create_spot_limit_orders_authz,
+    batch_update_orders_authz, cancel_derivative_order_authz, cancel_spot_order_authz,
+    create_derivative_limit_order_authz, create_derivative_market_order_authz,
+    create_spot_limit_order_authz, create_spot_market_order_authz, generic_authorization,
+    send_authorization, stake_authorization, Authz,
+};
 pub use bank::Bank;
-pub use exchange::Exchange;
-pub use gov::Gov;
+pub use exchange::{Exchange, OrderbookSnapshot, PriceLevel};
+pub use exchange_fixture::{ExchangeFixture, ExchangeFixtureBuilder, MarketSpec};
+pub use gov::{Gov, GovWithAppAccess, GOV_MODULE_ADDRESS};
 pub use insurance::Insurance;
 pub use oracle::Oracle;
 pub use staking::Staking;
 pub use tokenfactory::TokenFactory;
-pub use wasm::Wasm;
-pub use wasmx::Wasmx;
+pub use vesting::{Vesting, VestingSchedule};
+pub use wasm::{map_key, predict_instantiate2_address, Wasm};
+pub use wasmx::{BeginBlockerExecution, Wasmx};
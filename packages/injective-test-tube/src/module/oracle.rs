@@ -1,8 +1,18 @@
 use injective_std::types::injective::oracle::v1beta1::{
-    MsgRelayBandRates, MsgRelayBandRatesResponse, MsgRelayPriceFeedPrice,
-    MsgRelayPriceFeedPriceResponse, MsgRelayPythPrices, MsgRelayPythPricesResponse,
-    QueryModuleStateRequest, QueryModuleStateResponse, QueryOraclePriceRequest,
-    QueryOraclePriceResponse, QueryPythPriceRequest, QueryPythPriceResponse,
+    MsgRelayBandRates, MsgRelayBandRatesResponse, MsgRelayCoinbaseMessages,
+    MsgRelayCoinbaseMessagesResponse, MsgRelayPriceFeedPrice, MsgRelayPriceFeedPriceResponse,
+    MsgRelayProviderPrices, MsgRelayProviderPricesResponse, MsgRelayPythPrices,
+    MsgRelayPythPricesResponse, MsgRelayStorkPrices, MsgRelayStorkPricesResponse,
+    MsgRequestBandIbcRates, MsgRequestBandIbcRatesResponse, QueryBandPriceStatesRequest,
+    QueryBandPriceStatesResponse, QueryCoinbasePriceStatesRequest,
+    QueryCoinbasePriceStatesResponse, QueryHistoricalPriceRecordsRequest,
+    QueryHistoricalPriceRecordsResponse, QueryModuleStateRequest, QueryModuleStateResponse,
+    QueryOraclePriceRequest, QueryOraclePriceResponse, QueryOracleProviderPricesRequest,
+    QueryOracleProviderPricesResponse, QueryOracleProvidersInfoRequest,
+    QueryOracleProvidersInfoResponse, QueryOracleVolatilityRequest, QueryOracleVolatilityResponse,
+    QueryPriceFeedPriceStatesRequest, QueryPriceFeedPriceStatesResponse,
+    QueryProviderPriceStateRequest, QueryProviderPriceStateResponse, QueryPythPriceRequest,
+    QueryPythPriceResponse, QueryStorkPriceStatesRequest, QueryStorkPriceStatesResponse,
 };
 use test_tube_inj::module::Module;
 use test_tube_inj::runner::Runner;
@@ -34,6 +44,22 @@ where
         pub relay_pyth_prices: MsgRelayPythPrices => MsgRelayPythPricesResponse
     }
 
+    fn_execute! {
+        pub relay_coinbase_messages: MsgRelayCoinbaseMessages => MsgRelayCoinbaseMessagesResponse
+    }
+
+    fn_execute! {
+        pub relay_provider_prices: MsgRelayProviderPrices => MsgRelayProviderPricesResponse
+    }
+
+    fn_execute! {
+        pub relay_stork_prices: MsgRelayStorkPrices => MsgRelayStorkPricesResponse
+    }
+
+    fn_execute! {
+        pub request_band_ibc_rates: MsgRequestBandIbcRates => MsgRequestBandIbcRatesResponse
+    }
+
     fn_query! {
         pub query_module_state ["/injective.oracle.v1beta1.Query/OracleModuleState"]: QueryModuleStateRequest => QueryModuleStateResponse
     }
@@ -45,6 +71,42 @@ where
     fn_query! {
         pub query_pyth_price ["/injective.oracle.v1beta1.Query/PythPrice"]: QueryPythPriceRequest => QueryPythPriceResponse
     }
+
+    fn_query! {
+        pub query_price_feed_price_states ["/injective.oracle.v1beta1.Query/PriceFeedPriceStates"]: QueryPriceFeedPriceStatesRequest => QueryPriceFeedPriceStatesResponse
+    }
+
+    fn_query! {
+        pub query_provider_price_state ["/injective.oracle.v1beta1.Query/ProviderPriceState"]: QueryProviderPriceStateRequest => QueryProviderPriceStateResponse
+    }
+
+    fn_query! {
+        pub query_band_price_states ["/injective.oracle.v1beta1.Query/BandPriceStates"]: QueryBandPriceStatesRequest => QueryBandPriceStatesResponse
+    }
+
+    fn_query! {
+        pub query_coinbase_price_states ["/injective.oracle.v1beta1.Query/CoinbasePriceStates"]: QueryCoinbasePriceStatesRequest => QueryCoinbasePriceStatesResponse
+    }
+
+    fn_query! {
+        pub query_stork_price_states ["/injective.oracle.v1beta1.Query/StorkPriceStates"]: QueryStorkPriceStatesRequest => QueryStorkPriceStatesResponse
+    }
+
+    fn_query! {
+        pub query_historical_price_records ["/injective.oracle.v1beta1.Query/HistoricalPriceRecords"]: QueryHistoricalPriceRecordsRequest => QueryHistoricalPriceRecordsResponse
+    }
+
+    fn_query! {
+        pub query_oracle_volatility ["/injective.oracle.v1beta1.Query/OracleVolatility"]: QueryOracleVolatilityRequest => QueryOracleVolatilityResponse
+    }
+
+    fn_query! {
+        pub query_oracle_providers_info ["/injective.oracle.v1beta1.Query/OracleProvidersInfo"]: QueryOracleProvidersInfoRequest => QueryOracleProvidersInfoResponse
+    }
+
+    fn_query! {
+        pub query_oracle_provider_prices ["/injective.oracle.v1beta1.Query/OracleProviderPrices"]: QueryOracleProviderPricesRequest => QueryOracleProviderPricesResponse
+    }
 }
 
 #[cfg(test)]
@@ -60,11 +122,12 @@ mod tests {
             cosmos::{
                 bank::v1beta1::MsgSend,
                 base::v1beta1::Coin as TubeCoin,
-                gov::v1::{MsgSubmitProposal, MsgVote},
+                gov::v1::{MsgExecLegacyContent, MsgSubmitProposal, MsgVote},
             },
             injective::oracle,
             injective::oracle::v1beta1::{
-                GrantPriceFeederPrivilegeProposal, MsgRelayPriceFeedPrice, MsgUpdateParams,
+                GrantPriceFeederPrivilegeProposal, GrantProviderPrivilegeProposal,
+                MsgRelayPriceFeedPrice, MsgRelayProviderPrices, MsgUpdateParams,
             },
         },
     };
@@ -72,7 +135,10 @@ mod tests {
     use std::str::FromStr;
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use crate::{Account, Bank, Gov, InjectiveTestApp, Module, Oracle};
+    use crate::{
+        Account, Bank, Gov, GovWithAppAccess, InjectiveTestApp, Module, Oracle, RunnerError,
+        GOV_MODULE_ADDRESS,
+    };
 
     #[test]
     fn price_feed_oracle_integration() {
@@ -410,4 +476,86 @@ mod tests {
             "usdt publish_time should be equal to the price attestation"
         );
     }
+
+    #[test]
+    fn provider_oracle_integration() {
+        let app = InjectiveTestApp::new();
+        let oracle = Oracle::new(&app);
+
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+
+        GovWithAppAccess::new(&app)
+            .propose_and_execute(
+                MsgExecLegacyContent::TYPE_URL.to_string(),
+                MsgExecLegacyContent {
+                    content: Some(Any {
+                        type_url: GrantProviderPrivilegeProposal::TYPE_URL.to_string(),
+                        value: GrantProviderPrivilegeProposal {
+                            title: "grant provider privilege".to_string(),
+                            description: "grant provider privilege".to_string(),
+                            provider: "test-provider".to_string(),
+                            relayers: vec![signer.address()],
+                        }
+                        .encode_to_vec(),
+                    }),
+                    authority: GOV_MODULE_ADDRESS.to_string(),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        oracle
+            .relay_provider_prices(
+                MsgRelayProviderPrices {
+                    sender: signer.address(),
+                    provider: "test-provider".to_string(),
+                    symbols: vec!["INJ".to_string(), "ATOM".to_string()],
+                    prices: vec![
+                        "25000000000000000000".to_string(),
+                        "8000000000000000000".to_string(),
+                    ],
+                },
+                &signer,
+            )
+            .unwrap();
+
+        let state = oracle
+            .query_provider_price_state(&oracle::v1beta1::QueryProviderPriceStateRequest {
+                provider: "test-provider".to_string(),
+                symbol: "INJ".to_string(),
+            })
+            .unwrap()
+            .state
+            .unwrap();
+        assert_eq!(state.price, "25000000000000000000");
+
+        let providers = oracle
+            .query_oracle_providers_info(&oracle::v1beta1::QueryOracleProvidersInfoRequest {})
+            .unwrap()
+            .providers;
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].provider, "test-provider");
+        assert_eq!(providers[0].relayers, vec![signer.address()]);
+
+        // relaying without the provider privilege fails
+        let other = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        let err = oracle
+            .relay_provider_prices(
+                MsgRelayProviderPrices {
+                    sender: other.address(),
+                    provider: "test-provider".to_string(),
+                    symbols: vec!["INJ".to_string()],
+                    prices: vec!["1000000000000000000".to_string()],
+                },
+                &other,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, RunnerError::ExecuteError { ref codespace, .. } if codespace == "oracle")
+        );
+    }
 }
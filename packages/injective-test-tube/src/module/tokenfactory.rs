@@ -1,12 +1,20 @@
+use cosmwasm_std::Coin;
+use injective_std::types::cosmos::bank::v1beta1::MsgSend;
+use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
 use injective_std::types::injective::tokenfactory::v1beta1::{
     MsgBurn, MsgBurnResponse, MsgChangeAdmin, MsgChangeAdminResponse, MsgCreateDenom,
     MsgCreateDenomResponse, MsgMint, MsgMintResponse, MsgSetDenomMetadata,
-    MsgSetDenomMetadataResponse, QueryDenomAuthorityMetadataRequest,
-    QueryDenomAuthorityMetadataResponse, QueryDenomsFromCreatorRequest,
-    QueryDenomsFromCreatorResponse, QueryParamsRequest, QueryParamsResponse,
+    MsgSetDenomMetadataResponse, MsgUpdateParams, MsgUpdateParamsResponse,
+    QueryDenomAuthorityMetadataRequest, QueryDenomAuthorityMetadataResponse,
+    QueryDenomsFromCreatorRequest, QueryDenomsFromCreatorResponse, QueryModuleStateRequest,
+    QueryModuleStateResponse, QueryParamsRequest, QueryParamsResponse,
 };
+use prost::Message;
 
+use test_tube_inj::account::{Account, SigningAccount};
 use test_tube_inj::module::Module;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::{RunnerExecuteResult, RunnerResult};
 use test_tube_inj::runner::Runner;
 use test_tube_inj::{fn_execute, fn_query};
 
@@ -46,10 +54,100 @@ where
         pub set_denom_metadata: MsgSetDenomMetadata  ["/injective.tokenfactory.v1beta1.MsgSetDenomMetadata"]  => MsgSetDenomMetadataResponse
     }
 
+    // NOTE: the authority must be the gov module account, see `GovWithAppAccess::propose_and_execute`
+    fn_execute! {
+        pub update_params: MsgUpdateParams ["/injective.tokenfactory.v1beta1.MsgUpdateParams"] => MsgUpdateParamsResponse
+    }
+
+    /// Create `factory/{signer}/{subdenom}` with the given metadata and return the full denom.
+    pub fn create_denom_with_metadata(
+        &self,
+        subdenom: &str,
+        decimals: u32,
+        symbol: &str,
+        signer: &SigningAccount,
+    ) -> RunnerResult<String> {
+        let res = self.create_denom(
+            MsgCreateDenom {
+                sender: signer.address(),
+                subdenom: subdenom.to_string(),
+                name: symbol.to_string(),
+                symbol: symbol.to_string(),
+                decimals,
+            },
+            signer,
+        )?;
+
+        Ok(res.data.new_token_denom)
+    }
+
+    /// Mint `amount` and send it to `recipient` within the same transaction.
+    ///
+    /// `MsgMint` can only mint to the sender, so the minted coins are forwarded with a `MsgSend`.
+    pub fn mint_to(
+        &self,
+        amount: Coin,
+        recipient: &str,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgMintResponse> {
+        let amount = BaseCoin {
+            denom: amount.denom,
+            amount: amount.amount.to_string(),
+        };
+
+        let msgs = vec![
+            test_tube_inj::cosmrs::Any {
+                type_url: MsgMint::TYPE_URL.to_string(),
+                value: MsgMint {
+                    sender: signer.address(),
+                    amount: Some(amount.clone()),
+                }
+                .encode_to_vec(),
+            },
+            test_tube_inj::cosmrs::Any {
+                type_url: MsgSend::TYPE_URL.to_string(),
+                value: MsgSend {
+                    from_address: signer.address(),
+                    to_address: recipient.to_string(),
+                    amount: vec![amount],
+                }
+                .encode_to_vec(),
+            },
+        ];
+
+        self.runner.execute_multiple_raw(msgs, signer)
+    }
+
     fn_query! {
         pub query_params ["/injective.tokenfactory.v1beta1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
     }
 
+    /// Fee charged for every `MsgCreateDenom`.
+    pub fn query_denom_creation_fee(&self) -> RunnerResult<Vec<Coin>> {
+        let params = self
+            .query_params(&QueryParamsRequest {})?
+            .params
+            .unwrap_or_default();
+
+        params
+            .denom_creation_fee
+            .into_iter()
+            .map(|c| {
+                let amount = c.amount.parse::<u128>().map_err(|e| {
+                    RunnerError::GenericError(format!(
+                        "invalid denom creation fee amount {}: {}",
+                        c.amount, e
+                    ))
+                })?;
+                Ok(Coin::new(amount, c.denom))
+            })
+            .collect()
+    }
+
+    fn_query! {
+        pub query_module_state ["/injective.tokenfactory.v1beta1.Query/TokenfactoryModuleState"]: QueryModuleStateRequest => QueryModuleStateResponse
+    }
+
     fn_query! {
         pub query_denom_authority_metadata ["/injective.tokenfactory.v1beta1.Query/DenomAuthorityMetadata"]: QueryDenomAuthorityMetadataRequest => QueryDenomAuthorityMetadataResponse
     }
@@ -63,11 +161,16 @@ where
 mod tests {
     use cosmwasm_std::Coin;
     use injective_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
     use injective_std::types::injective::tokenfactory::v1beta1::{
-        MsgBurn, MsgCreateDenom, MsgMint, QueryDenomsFromCreatorRequest,
+        MsgBurn, MsgCreateDenom, MsgCreateDenomResponse, MsgMint, MsgMintResponse, MsgUpdateParams,
+        Params, QueryDenomsFromCreatorRequest, QueryModuleStateRequest,
     };
+    use prost::Message;
 
-    use crate::{Account, Bank, InjectiveTestApp, TokenFactory};
+    use crate::{
+        Account, Bank, GovWithAppAccess, InjectiveTestApp, Runner, TokenFactory, GOV_MODULE_ADDRESS,
+    };
     use test_tube_inj::Module;
 
     #[test]
@@ -161,4 +264,133 @@ mod tests {
         assert_eq!("0", balance.amount);
         assert_eq!(coin.denom, balance.denom);
     }
+
+    #[test]
+    fn tokenfactory_params_and_helpers() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        let receiver = app.init_account(&[Coin::new(1u128, "inj")]).unwrap();
+        let tokenfactory = TokenFactory::new(&app);
+        let bank = Bank::new(&app);
+
+        assert_eq!(
+            tokenfactory.query_denom_creation_fee().unwrap(),
+            vec![Coin::new(10_000_000_000_000_000_000u128, "inj")]
+        );
+
+        // lower the denom creation fee through governance
+        GovWithAppAccess::new(&app)
+            .propose_and_execute(
+                "/injective.tokenfactory.v1beta1.MsgUpdateParams".to_string(),
+                MsgUpdateParams {
+                    authority: GOV_MODULE_ADDRESS.to_string(),
+                    params: Some(Params {
+                        denom_creation_fee: vec![BaseCoin {
+                            amount: "1000".to_string(),
+                            denom: "inj".to_string(),
+                        }],
+                    }),
+                },
+                &signer,
+            )
+            .unwrap();
+
+        assert_eq!(
+            tokenfactory.query_denom_creation_fee().unwrap(),
+            vec![Coin::new(1_000u128, "inj")]
+        );
+
+        let denom = tokenfactory
+            .create_denom_with_metadata("ucoin", 6, "COIN", &signer)
+            .unwrap();
+        assert_eq!(denom, format!("factory/{}/ucoin", signer.address()));
+
+        tokenfactory
+            .mint_to(
+                Coin::new(1_000_000u128, &denom),
+                &receiver.address(),
+                &signer,
+            )
+            .unwrap();
+
+        let balance = bank
+            .query_balance(&QueryBalanceRequest {
+                address: receiver.address(),
+                denom: denom.clone(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000000");
+
+        let state = tokenfactory
+            .query_module_state(&QueryModuleStateRequest {})
+            .unwrap()
+            .state
+            .unwrap();
+        assert!(state.factory_denoms.iter().any(|d| d.denom == denom));
+    }
+
+    #[test]
+    fn create_denom_and_mint_in_one_tx() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+
+        let denom = format!("factory/{}/ubatch", signer.address());
+        let res = app
+            .execute_multiple_typed(
+                vec![
+                    test_tube_inj::cosmrs::Any {
+                        type_url: MsgCreateDenom::TYPE_URL.to_string(),
+                        value: MsgCreateDenom {
+                            sender: signer.address(),
+                            subdenom: "ubatch".to_string(),
+                            name: "Batch".to_string(),
+                            symbol: "BATCH".to_string(),
+                            decimals: 6,
+                        }
+                        .encode_to_vec(),
+                    },
+                    test_tube_inj::cosmrs::Any {
+                        type_url: MsgMint::TYPE_URL.to_string(),
+                        value: MsgMint {
+                            sender: signer.address(),
+                            amount: Some(BaseCoin {
+                                denom: denom.clone(),
+                                amount: "1000".to_string(),
+                            }),
+                        }
+                        .encode_to_vec(),
+                    },
+                ],
+                &signer,
+            )
+            .unwrap();
+
+        assert_eq!(
+            res.msg_response_type_urls(),
+            vec![MsgCreateDenomResponse::TYPE_URL, MsgMintResponse::TYPE_URL]
+        );
+
+        let create_denom_response = res
+            .decode_msg_response::<MsgCreateDenomResponse>(0)
+            .unwrap();
+        assert_eq!(create_denom_response.new_token_denom, denom);
+        res.decode_msg_response::<MsgMintResponse>(1).unwrap();
+        res.decode_msg_response::<MsgMintResponse>(2).unwrap_err();
+
+        let balance = Bank::new(&app)
+            .query_balance(&QueryBalanceRequest {
+                address: signer.address(),
+                denom,
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000");
+    }
 }
@@ -0,0 +1,308 @@
+use cosmwasm_std::{Coin, Uint128};
+use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+use injective_std::types::cosmos::vesting::v1beta1::{
+    MsgCreatePeriodicVestingAccount, MsgCreatePeriodicVestingAccountResponse,
+    MsgCreatePermanentLockedAccount, MsgCreatePermanentLockedAccountResponse,
+    MsgCreateVestingAccount, MsgCreateVestingAccountResponse, Period,
+};
+use test_tube_inj::account::{Account, SigningAccount};
+use test_tube_inj::fn_execute;
+use test_tube_inj::module::Module;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::RunnerResult;
+use test_tube_inj::runner::Runner;
+
+/// Unlock schedule used by [`Vesting::create_vesting_account_with_schedule`].
+/// All times are unix timestamps in seconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VestingSchedule {
+    /// Coins vest linearly from the current block time until `end_time`.
+    Continuous { end_time: i64 },
+    /// All coins vest at once at `end_time`.
+    Delayed { end_time: i64 },
+    /// Coins vest in `periods` equal parts, one every `period_length` seconds
+    /// starting from `start_time`. The remainder is spread over the first periods,
+    /// and every period must unlock some coins.
+    Periodic {
+        start_time: i64,
+        period_length: i64,
+        periods: u32,
+    },
+    /// Coins never vest, but can still be delegated.
+    PermanentLocked,
+}
+
+pub struct Vesting<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Vesting<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Vesting<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub create_vesting_account: MsgCreateVestingAccount["/cosmos.vesting.v1beta1.MsgCreateVestingAccount"] => MsgCreateVestingAccountResponse
+    }
+
+    fn_execute! {
+        pub create_permanent_locked_account: MsgCreatePermanentLockedAccount["/cosmos.vesting.v1beta1.MsgCreatePermanentLockedAccount"] => MsgCreatePermanentLockedAccountResponse
+    }
+
+    fn_execute! {
+        pub create_periodic_vesting_account: MsgCreatePeriodicVestingAccount["/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount"] => MsgCreatePeriodicVestingAccountResponse
+    }
+
+    /// Create a new vesting account at `to_address`, funded by `signer` with `coins`
+    /// that unlock according to `schedule`.
+    pub fn create_vesting_account_with_schedule(
+        &self,
+        to_address: &str,
+        coins: &[Coin],
+        schedule: &VestingSchedule,
+        signer: &SigningAccount,
+    ) -> RunnerResult<()> {
+        match schedule {
+            VestingSchedule::Continuous { end_time } | VestingSchedule::Delayed { end_time } => {
+                self.create_vesting_account(
+                    MsgCreateVestingAccount {
+                        from_address: signer.address(),
+                        to_address: to_address.to_string(),
+                        amount: coins_to_proto(coins),
+                        end_time: *end_time,
+                        delayed: matches!(schedule, VestingSchedule::Delayed { .. }),
+                    },
+                    signer,
+                )?;
+            }
+            VestingSchedule::Periodic {
+                start_time,
+                period_length,
+                periods,
+            } => {
+                self.create_periodic_vesting_account(
+                    MsgCreatePeriodicVestingAccount {
+                        from_address: signer.address(),
+                        to_address: to_address.to_string(),
+                        start_time: *start_time,
+                        vesting_periods: split_into_periods(coins, *period_length, *periods)?,
+                    },
+                    signer,
+                )?;
+            }
+            VestingSchedule::PermanentLocked => {
+                self.create_permanent_locked_account(
+                    MsgCreatePermanentLockedAccount {
+                        from_address: signer.address(),
+                        to_address: to_address.to_string(),
+                        amount: coins_to_proto(coins),
+                    },
+                    signer,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn coins_to_proto(coins: &[Coin]) -> Vec<BaseCoin> {
+    // invalid coins if denom are unsorted
+    let mut coins = coins.to_vec();
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    coins
+        .iter()
+        .filter(|c| !c.amount.is_zero())
+        .map(|c| BaseCoin {
+            denom: c.denom.clone(),
+            amount: c.amount.to_string(),
+        })
+        .collect()
+}
+
+fn split_into_periods(
+    coins: &[Coin],
+    period_length: i64,
+    periods: u32,
+) -> RunnerResult<Vec<Period>> {
+    if periods == 0 || period_length <= 0 {
+        return Err(RunnerError::GenericError(format!(
+            "invalid vesting schedule of {} periods of {} seconds",
+            periods, period_length
+        )));
+    }
+    let count = Uint128::from(periods);
+
+    (0..periods)
+        .map(|i| {
+            let amount = coins
+                .iter()
+                .map(|c| {
+                    // the first periods take one more coin each until the remainder is spent
+                    let share = c.amount / count;
+                    if Uint128::from(i) < c.amount % count {
+                        Coin::new(share + Uint128::one(), &c.denom)
+                    } else {
+                        Coin::new(share, &c.denom)
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let amount = coins_to_proto(&amount);
+            if amount.is_empty() {
+                return Err(RunnerError::GenericError(format!(
+                    "vesting period {} of {} would unlock no coins",
+                    i + 1,
+                    periods
+                )));
+            }
+
+            Ok(Period {
+                length: period_length,
+                amount,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use injective_std::types::cosmos::bank::v1beta1::{
+        MsgSend, QueryBalanceRequest, QuerySpendableBalancesRequest,
+    };
+    use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+
+    use crate::{Account, Bank, InjectiveTestApp, Module, RunnerError, VestingSchedule};
+
+    use super::split_into_periods;
+
+    #[test]
+    fn split_coins_into_periods() {
+        let amounts = |coins: &[Coin], periods: u32| {
+            split_into_periods(coins, 100, periods)
+                .unwrap()
+                .into_iter()
+                .map(|period| {
+                    assert_eq!(period.length, 100);
+                    period
+                        .amount
+                        .into_iter()
+                        .map(|c| format!("{}{}", c.amount, c.denom))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            amounts(&[Coin::new(10u128, "usdt")], 4),
+            ["3usdt", "3usdt", "2usdt", "2usdt"]
+        );
+        assert_eq!(
+            amounts(&[Coin::new(5u128, "usdt"), Coin::new(2u128, "inj")], 3),
+            ["1inj,2usdt", "1inj,2usdt", "1usdt"]
+        );
+
+        for (coins, period_length, periods) in [
+            (vec![Coin::new(10u128, "usdt")], 100, 0),
+            (vec![Coin::new(10u128, "usdt")], 0, 4),
+            (vec![Coin::new(10u128, "usdt")], -100, 4),
+            (vec![Coin::new(3u128, "usdt")], 100, 4),
+        ] {
+            assert!(matches!(
+                split_into_periods(&coins, period_length, periods),
+                Err(RunnerError::GenericError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn vesting_integration() {
+        let app = InjectiveTestApp::new();
+        let bank = Bank::new(&app);
+
+        let receiver = app.init_account(&[Coin::new(1u128, "inj")]).unwrap();
+
+        let start_time = app.get_block_time_seconds();
+        let vesting_account = app
+            .init_vesting_account(
+                &[Coin::new(1_000u128, "usdt")],
+                VestingSchedule::Periodic {
+                    start_time,
+                    period_length: 100,
+                    periods: 4,
+                },
+            )
+            .unwrap();
+
+        let query_spendable_usdt = |address: String| {
+            bank.query_spendable_balances(&QuerySpendableBalancesRequest {
+                address,
+                pagination: None,
+            })
+            .unwrap()
+            .balances
+            .into_iter()
+            .find(|c| c.denom == "usdt")
+            .map(|c| c.amount)
+            .unwrap_or_else(|| "0".to_string())
+        };
+
+        let balance = bank
+            .query_balance(&QueryBalanceRequest {
+                address: vesting_account.address(),
+                denom: "usdt".to_string(),
+            })
+            .unwrap()
+            .balance
+            .unwrap();
+        assert_eq!(balance.amount, "1000");
+        assert_eq!(query_spendable_usdt(vesting_account.address()), "0");
+
+        // the vesting account needs spendable funds to pay for fees
+        let funder = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        bank.send(
+            MsgSend {
+                from_address: funder.address(),
+                to_address: vesting_account.address(),
+                amount: vec![BaseCoin {
+                    amount: 1_000_000_000_000_000_000u128.to_string(),
+                    denom: "inj".to_string(),
+                }],
+            },
+            &funder,
+        )
+        .unwrap();
+
+        // move past the first two periods
+        app.increase_time(200);
+        assert_eq!(query_spendable_usdt(vesting_account.address()), "500");
+
+        let send = |amount: u128| {
+            bank.send(
+                MsgSend {
+                    from_address: vesting_account.address(),
+                    to_address: receiver.address(),
+                    amount: vec![BaseCoin {
+                        amount: amount.to_string(),
+                        denom: "usdt".to_string(),
+                    }],
+                },
+                &vesting_account,
+            )
+        };
+
+        send(600).unwrap_err();
+        send(500).unwrap();
+        assert_eq!(query_spendable_usdt(receiver.address()), "500");
+    }
+}
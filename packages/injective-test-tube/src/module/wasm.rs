@@ -1,17 +1,38 @@
-use cosmwasm_std::Coin;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+use cosmrs::AccountId;
+use cosmwasm_std::{instantiate2_address, CanonicalAddr, Coin};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use injective_std::types::cosmos::base::query::v1beta1::PageRequest;
 use injective_std::types::cosmwasm::wasm::v1::{
-    AccessConfig, MsgExecuteContract, MsgExecuteContractResponse, MsgInstantiateContract,
-    MsgInstantiateContractResponse, MsgMigrateContract, MsgMigrateContractResponse, MsgStoreCode,
-    MsgStoreCodeResponse, QuerySmartContractStateRequest, QuerySmartContractStateResponse,
+    AccessConfig, MsgClearAdmin, MsgClearAdminResponse, MsgExecuteContract,
+    MsgExecuteContractResponse, MsgInstantiateContract, MsgInstantiateContract2,
+    MsgInstantiateContract2Response, MsgInstantiateContractResponse, MsgMigrateContract,
+    MsgMigrateContractResponse, MsgPinCodes, MsgStoreCode, MsgStoreCodeResponse, MsgUnpinCodes,
+    MsgUpdateAdmin, MsgUpdateAdminResponse, MsgUpdateInstantiateConfig,
+    MsgUpdateInstantiateConfigResponse, QueryAllContractStateRequest,
+    QueryAllContractStateResponse, QueryCodeRequest, QueryCodeResponse, QueryCodesRequest,
+    QueryCodesResponse, QueryContractHistoryRequest, QueryContractHistoryResponse,
+    QueryContractInfoRequest, QueryContractInfoResponse, QueryContractsByCodeRequest,
+    QueryContractsByCodeResponse, QueryContractsByCreatorRequest, QueryContractsByCreatorResponse,
+    QueryParamsRequest, QueryParamsResponse, QueryPinnedCodesRequest, QueryPinnedCodesResponse,
+    QueryRawContractStateRequest, QueryRawContractStateResponse, QuerySmartContractStateRequest,
+    QuerySmartContractStateResponse,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
 use test_tube_inj::runner::error::{DecodeError, EncodeError, RunnerError};
-use test_tube_inj::runner::result::{RunnerExecuteResult, RunnerResult};
+use test_tube_inj::runner::result::{RunnerExecuteResult, RunnerResult, SudoResponse};
 use test_tube_inj::{
     account::{Account, SigningAccount},
     runner::Runner,
 };
+use test_tube_inj::{fn_execute, fn_query};
+
+use crate::{GovWithAppAccess, InjectiveTestApp, GOV_MODULE_ADDRESS};
 
 pub struct Wasm<'a, R: Runner<'a>> {
     runner: &'a R,
@@ -44,6 +65,72 @@ where
         )
     }
 
+    /// Store the code of a `.wasm` file, or of an already gzipped `.wasm.gz` file.
+    pub fn store_code_from_file(
+        &self,
+        path: impl AsRef<Path>,
+        instantiate_permission: Option<AccessConfig>,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgStoreCodeResponse> {
+        let wasm_byte_code = read_file(path.as_ref())?;
+        self.store_code(&wasm_byte_code, instantiate_permission, signer)
+    }
+
+    /// Store the code of every `.wasm` and `.wasm.gz` file in `dir`, returning the code ids
+    /// by file name without extension, e.g. `cw1_whitelist`.
+    ///
+    /// Files are stored in name order, so code ids are stable across runs. With `gzip` set,
+    /// `.wasm` payloads are compressed before upload, which saves a lot of gas. Nothing is
+    /// stored if two files share a name, e.g. `foo.wasm` and `foo.wasm.gz`.
+    pub fn store_all(
+        &self,
+        dir: impl AsRef<Path>,
+        gzip: bool,
+        signer: &SigningAccount,
+    ) -> RunnerResult<HashMap<String, u64>> {
+        let mut files = std::fs::read_dir(dir.as_ref())
+            .map_err(|e| {
+                RunnerError::GenericError(format!(
+                    "unable to read {}: {}",
+                    dir.as_ref().display(),
+                    e
+                ))
+            })?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter_map(|path| {
+                let file_name = path.file_name()?.to_str()?;
+                let name = file_name
+                    .strip_suffix(".wasm")
+                    .or_else(|| file_name.strip_suffix(".wasm.gz"))?
+                    .to_string();
+                Some((name, path))
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+
+        if let Some(pair) = files.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(RunnerError::GenericError(format!(
+                "{} and {} are both stored as {}",
+                pair[0].1.display(),
+                pair[1].1.display(),
+                pair[0].0
+            )));
+        }
+
+        let mut code_ids = HashMap::with_capacity(files.len());
+        for (name, path) in files {
+            let mut wasm_byte_code = read_file(&path)?;
+            if gzip && path.extension().is_some_and(|ext| ext == "wasm") {
+                wasm_byte_code = gzip_compress(&wasm_byte_code)?;
+            }
+
+            let code_id = self.store_code(&wasm_byte_code, None, signer)?.data.code_id;
+            code_ids.insert(name, code_id);
+        }
+
+        Ok(code_ids)
+    }
+
     pub fn instantiate<M>(
         &self,
         code_id: u64,
@@ -76,6 +163,46 @@ where
         )
     }
 
+    /// Instantiate a contract at an address derived from the code checksum, the signer and
+    /// `salt`, see [`predict_instantiate2_address`]. With `fix_msg` set, the instantiate
+    /// message is part of the derivation as well.
+    #[allow(clippy::too_many_arguments)]
+    pub fn instantiate2<M>(
+        &self,
+        code_id: u64,
+        msg: &M,
+        salt: &[u8],
+        fix_msg: bool,
+        admin: Option<&str>,
+        label: Option<&str>,
+        funds: &[Coin],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgInstantiateContract2Response>
+    where
+        M: ?Sized + Serialize,
+    {
+        self.runner.execute(
+            MsgInstantiateContract2 {
+                sender: signer.address(),
+                admin: admin.unwrap_or_default().to_string(),
+                code_id,
+                label: label.unwrap_or(" ").to_string(), // empty string causes panic
+                msg: serde_json::to_vec(msg).map_err(EncodeError::JsonEncodeError)?,
+                funds: funds
+                    .iter()
+                    .map(|c| injective_std::types::cosmos::base::v1beta1::Coin {
+                        denom: c.denom.parse().unwrap(),
+                        amount: format!("{}", c.amount.u128()),
+                    })
+                    .collect(),
+                salt: salt.to_vec(),
+                fix_msg,
+            },
+            "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+            signer,
+        )
+    }
+
     pub fn execute<M>(
         &self,
         contract: &str,
@@ -126,6 +253,129 @@ where
         )
     }
 
+    fn_execute! {
+        pub update_admin: MsgUpdateAdmin => MsgUpdateAdminResponse
+    }
+
+    fn_execute! {
+        pub clear_admin: MsgClearAdmin => MsgClearAdminResponse
+    }
+
+    fn_execute! {
+        pub update_instantiate_config: MsgUpdateInstantiateConfig => MsgUpdateInstantiateConfigResponse
+    }
+
+    fn_query! {
+        pub query_contract_info ["/cosmwasm.wasm.v1.Query/ContractInfo"]: QueryContractInfoRequest => QueryContractInfoResponse
+    }
+
+    fn_query! {
+        pub query_contract_history ["/cosmwasm.wasm.v1.Query/ContractHistory"]: QueryContractHistoryRequest => QueryContractHistoryResponse
+    }
+
+    fn_query! {
+        pub query_contracts_by_code ["/cosmwasm.wasm.v1.Query/ContractsByCode"]: QueryContractsByCodeRequest => QueryContractsByCodeResponse
+    }
+
+    fn_query! {
+        pub query_contracts_by_creator ["/cosmwasm.wasm.v1.Query/ContractsByCreator"]: QueryContractsByCreatorRequest => QueryContractsByCreatorResponse
+    }
+
+    fn_query! {
+        pub query_code ["/cosmwasm.wasm.v1.Query/Code"]: QueryCodeRequest => QueryCodeResponse
+    }
+
+    fn_query! {
+        pub query_codes ["/cosmwasm.wasm.v1.Query/Codes"]: QueryCodesRequest => QueryCodesResponse
+    }
+
+    fn_query! {
+        pub query_pinned_codes ["/cosmwasm.wasm.v1.Query/PinnedCodes"]: QueryPinnedCodesRequest => QueryPinnedCodesResponse
+    }
+
+    fn_query! {
+        pub query_params ["/cosmwasm.wasm.v1.Query/Params"]: QueryParamsRequest => QueryParamsResponse
+    }
+
+    fn_query! {
+        pub query_all_contract_state ["/cosmwasm.wasm.v1.Query/AllContractState"]: QueryAllContractStateRequest => QueryAllContractStateResponse
+    }
+
+    /// Raw value stored by `contract` under `key`, `None` if there is none.
+    pub fn query_raw(&self, contract: &str, key: &[u8]) -> RunnerResult<Option<Vec<u8>>> {
+        let res = self
+            .runner
+            .query::<QueryRawContractStateRequest, QueryRawContractStateResponse>(
+                "/cosmwasm.wasm.v1.Query/RawContractState",
+                &QueryRawContractStateRequest {
+                    address: contract.to_owned(),
+                    query_data: key.to_vec(),
+                },
+            )?;
+
+        Ok(Some(res.data).filter(|data| !data.is_empty()))
+    }
+
+    /// Every key and value stored by `contract`, fetched `page_size` entries at a time.
+    pub fn all_contract_state(
+        &self,
+        contract: &str,
+        page_size: u64,
+    ) -> RunnerResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut state = vec![];
+        let mut next_key = vec![];
+        loop {
+            let res = self.query_all_contract_state(&QueryAllContractStateRequest {
+                address: contract.to_owned(),
+                pagination: Some(PageRequest {
+                    key: next_key,
+                    offset: 0,
+                    limit: page_size,
+                    count_total: false,
+                    reverse: false,
+                }),
+            })?;
+            state.extend(res.models.into_iter().map(|m| (m.key, m.value)));
+
+            match res.pagination.map(|p| p.next_key) {
+                Some(key) if !key.is_empty() => next_key = key,
+                _ => return Ok(state),
+            }
+        }
+    }
+
+    /// Value of a `cw-storage-plus` `Item` stored under `namespace`.
+    pub fn query_item<T>(&self, contract: &str, namespace: &str) -> RunnerResult<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.query_json(contract, namespace.as_bytes())
+    }
+
+    /// Value of a `cw-storage-plus` `Map` entry, see [`map_key`] for the encoding of `key`.
+    pub fn query_map_value<T>(
+        &self,
+        contract: &str,
+        namespace: &str,
+        key: &[&[u8]],
+    ) -> RunnerResult<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.query_json(contract, &map_key(namespace, key))
+    }
+
+    fn query_json<T>(&self, contract: &str, key: &[u8]) -> RunnerResult<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.query_raw(contract, key)?
+            .map(|value| serde_json::from_slice(&value))
+            .transpose()
+            .map_err(DecodeError::JsonDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+
     pub fn query<M, Res>(&self, contract: &str, msg: &M) -> RunnerResult<Res>
     where
         M: ?Sized + Serialize,
@@ -146,3 +396,591 @@ where
             .map_err(RunnerError::DecodeError)
     }
 }
+
+impl<'a> Wasm<'a, InjectiveTestApp> {
+    /// Call the sudo entry point of `contract` through the wasm keeper, as wasmx or gov would,
+    /// returning the response data and the gas used.
+    pub fn sudo<M>(&self, contract: &str, msg: &M) -> RunnerResult<SudoResponse>
+    where
+        M: ?Sized + Serialize,
+    {
+        let msg = serde_json::to_string(msg).map_err(EncodeError::JsonEncodeError)?;
+        self.runner.sudo(contract, &msg)
+    }
+
+    /// Pin `code_ids` in the wasm VM cache through a gov proposal submitted by `proposer`.
+    pub fn pin_code(&self, code_ids: &[u64], proposer: &SigningAccount) -> RunnerResult<()> {
+        GovWithAppAccess::new(self.runner).propose_and_execute(
+            MsgPinCodes::TYPE_URL.to_string(),
+            MsgPinCodes {
+                authority: GOV_MODULE_ADDRESS.to_string(),
+                code_ids: code_ids.to_vec(),
+            },
+            proposer,
+        )?;
+
+        Ok(())
+    }
+
+    /// Unpin `code_ids` from the wasm VM cache through a gov proposal submitted by `proposer`.
+    pub fn unpin_code(&self, code_ids: &[u64], proposer: &SigningAccount) -> RunnerResult<()> {
+        GovWithAppAccess::new(self.runner).propose_and_execute(
+            MsgUnpinCodes::TYPE_URL.to_string(),
+            MsgUnpinCodes {
+                authority: GOV_MODULE_ADDRESS.to_string(),
+                code_ids: code_ids.to_vec(),
+            },
+            proposer,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Address of a contract instantiated with `instantiate2` (without `fix_msg`) by `creator`,
+/// for the code with `checksum`, i.e. the `data_hash` of its code info.
+pub fn predict_instantiate2_address(
+    checksum: &[u8],
+    creator: &str,
+    salt: &[u8],
+) -> RunnerResult<String> {
+    let creator = creator
+        .parse::<AccountId>()
+        .map_err(|e| RunnerError::GenericError(format!("invalid creator address: {}", e)))?;
+    let address = instantiate2_address(checksum, &CanonicalAddr::from(creator.to_bytes()), salt)
+        .map_err(|e| RunnerError::GenericError(e.to_string()))?;
+
+    AccountId::new(creator.prefix(), address.as_slice())
+        .map(|address| address.to_string())
+        .map_err(|e| RunnerError::GenericError(e.to_string()))
+}
+
+/// Storage key of a `cw-storage-plus` `Map` entry under `namespace`.
+///
+/// `key` holds the raw bytes of each element of the map key, e.g. the address bytes of
+/// `&Addr` keys or the big endian bytes of integer keys, so `&[b"inj1..."]` for a
+/// `Map<&Addr, _>` and `&[b"inj1...", &7u64.to_be_bytes()]` for a `Map<(&Addr, u64), _>`.
+pub fn map_key(namespace: &str, key: &[&[u8]]) -> Vec<u8> {
+    let (last, prefixes): (&[u8], &[&[u8]]) = match key.split_last() {
+        Some((last, prefixes)) => (last, prefixes),
+        None => (&[], &[]),
+    };
+
+    let mut full_key = vec![];
+    for prefix in std::iter::once(namespace.as_bytes()).chain(prefixes.iter().copied()) {
+        full_key.extend_from_slice(&(prefix.len() as u16).to_be_bytes());
+        full_key.extend_from_slice(prefix);
+    }
+    full_key.extend_from_slice(last);
+    full_key
+}
+
+fn read_file(path: &Path) -> RunnerResult<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| RunnerError::GenericError(format!("unable to read {}: {}", path.display(), e)))
+}
+
+fn gzip_compress(bytes: &[u8]) -> RunnerResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(bytes)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| RunnerError::GenericError(format!("unable to gzip wasm code: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use cw1_whitelist::msg::InstantiateMsg;
+    use injective_std::types::cosmwasm::wasm::v1::{
+        AccessConfig, AccessType, ContractCodeHistoryOperationType, MsgClearAdmin, MsgUpdateAdmin,
+        MsgUpdateInstantiateConfig, QueryCodeRequest, QueryCodesRequest,
+        QueryContractHistoryRequest, QueryContractInfoRequest, QueryContractsByCodeRequest,
+        QueryContractsByCreatorRequest, QueryParamsRequest, QueryPinnedCodesRequest,
+    };
+
+    use crate::{
+        map_key, predict_instantiate2_address, Account, InjectiveTestApp, Module, RunnerError, Wasm,
+    };
+
+    use super::gzip_compress;
+
+    #[test]
+    fn store_code_from_files() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        let wasm = Wasm::new(&app);
+
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, &signer)
+            .unwrap()
+            .data
+            .code_id;
+        assert_eq!(code_id, 1);
+
+        let err = wasm
+            .store_code_from_file("./test_artifacts/missing.wasm", None, &signer)
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::GenericError(_)));
+
+        let code_ids = wasm.store_all("./test_artifacts", false, &signer).unwrap();
+        assert_eq!(code_ids.len(), 2);
+        assert_eq!(code_ids["cw1_subkeys"], 2);
+        assert_eq!(code_ids["cw1_whitelist"], 3);
+
+        let gzipped_code_ids = wasm.store_all("./test_artifacts", true, &signer).unwrap();
+        assert_eq!(gzipped_code_ids["cw1_subkeys"], 4);
+        assert_eq!(gzipped_code_ids["cw1_whitelist"], 5);
+
+        // gzipped payloads are stored uncompressed
+        let code = |code_id: u64| wasm.query_code(&QueryCodeRequest { code_id }).unwrap().data;
+        assert_eq!(code(3), code(5));
+
+        let dir = std::env::temp_dir().join(format!("store_all_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        std::fs::write(dir.join("foo.wasm"), &wasm_byte_code).unwrap();
+        std::fs::write(
+            dir.join("foo.wasm.gz"),
+            gzip_compress(&wasm_byte_code).unwrap(),
+        )
+        .unwrap();
+
+        let err = wasm.store_all(&dir, false, &signer).unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(err, RunnerError::GenericError(_)));
+        // nothing was stored
+        assert!(wasm.query_code(&QueryCodeRequest { code_id: 6 }).is_err());
+    }
+
+    #[test]
+    fn instantiate2_at_predicted_address() {
+        let app = InjectiveTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        let wasm = Wasm::new(&app);
+
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, &signer)
+            .unwrap()
+            .data
+            .code_id;
+        let checksum = wasm
+            .query_code(&QueryCodeRequest { code_id })
+            .unwrap()
+            .code_info
+            .unwrap()
+            .data_hash;
+
+        let salt = b"child-1";
+        let predicted = predict_instantiate2_address(&checksum, &signer.address(), salt).unwrap();
+        assert!(predicted.starts_with("inj1"));
+
+        let address = wasm
+            .instantiate2(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![signer.address()],
+                    mutable: false,
+                },
+                salt,
+                false,
+                None,
+                Some("child"),
+                &[],
+                &signer,
+            )
+            .unwrap()
+            .data
+            .address;
+        assert_eq!(address, predicted);
+
+        // the same salt can't be used twice
+        let err = wasm
+            .instantiate2(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![],
+                    mutable: false,
+                },
+                salt,
+                false,
+                None,
+                Some("child"),
+                &[],
+                &signer,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RunnerError::ExecuteError { ref codespace, code: 14, .. } if codespace == "wasm"
+        ));
+    }
+
+    #[test]
+    fn admin_management_and_metadata_queries() {
+        let app = InjectiveTestApp::new();
+        let accs = app
+            .init_accounts(&[Coin::new(100_000_000_000_000_000_000u128, "inj")], 2)
+            .unwrap();
+        let (owner, new_admin) = (&accs[0], &accs[1]);
+        let wasm = Wasm::new(&app);
+
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, owner)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_address = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![owner.address()],
+                    mutable: false,
+                },
+                Some(&owner.address()),
+                Some("whitelist"),
+                &[],
+                owner,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let contract_info = |address: &str| {
+            wasm.query_contract_info(&QueryContractInfoRequest {
+                address: address.to_string(),
+            })
+            .unwrap()
+            .contract_info
+            .unwrap()
+        };
+        let info = contract_info(&contract_address);
+        assert_eq!(info.code_id, code_id);
+        assert_eq!(info.creator, owner.address());
+        assert_eq!(info.admin, owner.address());
+        assert_eq!(info.label, "whitelist");
+
+        wasm.update_admin(
+            MsgUpdateAdmin {
+                sender: owner.address(),
+                new_admin: new_admin.address(),
+                contract: contract_address.clone(),
+            },
+            owner,
+        )
+        .unwrap();
+        assert_eq!(contract_info(&contract_address).admin, new_admin.address());
+
+        // only the current admin can clear it
+        wasm.clear_admin(
+            MsgClearAdmin {
+                sender: owner.address(),
+                contract: contract_address.clone(),
+            },
+            owner,
+        )
+        .unwrap_err();
+        wasm.clear_admin(
+            MsgClearAdmin {
+                sender: new_admin.address(),
+                contract: contract_address.clone(),
+            },
+            new_admin,
+        )
+        .unwrap();
+        assert_eq!(contract_info(&contract_address).admin, "");
+
+        let history = wasm
+            .query_contract_history(&QueryContractHistoryRequest {
+                address: contract_address.clone(),
+                pagination: None,
+            })
+            .unwrap()
+            .entries;
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            history[0].operation,
+            ContractCodeHistoryOperationType::Init as i32
+        );
+        assert_eq!(history[0].code_id, code_id);
+
+        let contracts = wasm
+            .query_contracts_by_code(&QueryContractsByCodeRequest {
+                code_id,
+                pagination: None,
+            })
+            .unwrap()
+            .contracts;
+        assert_eq!(contracts, vec![contract_address.clone()]);
+
+        let contracts = wasm
+            .query_contracts_by_creator(&QueryContractsByCreatorRequest {
+                creator_address: owner.address(),
+                pagination: None,
+            })
+            .unwrap()
+            .contract_addresses;
+        assert_eq!(contracts, vec![contract_address]);
+
+        let codes = wasm
+            .query_codes(&QueryCodesRequest { pagination: None })
+            .unwrap()
+            .code_infos;
+        assert!(codes.iter().any(|c| c.code_id == code_id));
+
+        let pinned = wasm
+            .query_pinned_codes(&QueryPinnedCodesRequest { pagination: None })
+            .unwrap()
+            .code_ids;
+        assert!(!pinned.contains(&code_id));
+
+        assert!(wasm
+            .query_params(&QueryParamsRequest {})
+            .unwrap()
+            .params
+            .is_some());
+
+        wasm.update_instantiate_config(
+            MsgUpdateInstantiateConfig {
+                sender: owner.address(),
+                code_id,
+                new_instantiate_permission: Some(AccessConfig {
+                    permission: AccessType::Nobody as i32,
+                    addresses: vec![],
+                }),
+            },
+            owner,
+        )
+        .unwrap();
+
+        let permission = wasm
+            .query_code(&QueryCodeRequest { code_id })
+            .unwrap()
+            .code_info
+            .unwrap()
+            .instantiate_permission
+            .unwrap();
+        assert_eq!(permission.permission, AccessType::Nobody as i32);
+
+        wasm.instantiate(
+            code_id,
+            &InstantiateMsg {
+                admins: vec![],
+                mutable: false,
+            },
+            None,
+            None,
+            &[],
+            owner,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn raw_and_typed_contract_storage() {
+        let app = InjectiveTestApp::new();
+        let accs = app
+            .init_accounts(&[Coin::new(100_000_000_000_000_000_000u128, "inj")], 2)
+            .unwrap();
+        let (admin, spender) = (&accs[0], &accs[1]);
+        let wasm = Wasm::new(&app);
+
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_subkeys.wasm", None, admin)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_address = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![admin.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                admin,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        wasm.execute(
+            &contract_address,
+            &cw1_subkeys::msg::ExecuteMsg::<cosmwasm_std::Empty>::IncreaseAllowance {
+                spender: spender.address(),
+                amount: Coin::new(1_000u128, "inj"),
+                expires: None,
+            },
+            &[],
+            admin,
+        )
+        .unwrap();
+
+        let raw = wasm
+            .query_raw(&contract_address, b"admin_list")
+            .unwrap()
+            .unwrap();
+        let admin_list: serde_json::Value = serde_json::from_slice(&raw).unwrap();
+        assert_eq!(admin_list["admins"][0], admin.address());
+        assert!(wasm
+            .query_raw(&contract_address, b"missing")
+            .unwrap()
+            .is_none());
+
+        let admin_list: serde_json::Value = wasm
+            .query_item(&contract_address, "admin_list")
+            .unwrap()
+            .unwrap();
+        assert_eq!(admin_list["mutable"], true);
+
+        let allowance: serde_json::Value = wasm
+            .query_map_value(
+                &contract_address,
+                "allowance",
+                &[spender.address().as_bytes()],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(allowance["balance"][0]["amount"], "1000");
+
+        // a page size of 1 forces pagination through every entry
+        let state = wasm.all_contract_state(&contract_address, 1).unwrap();
+        assert!(state.len() > 2);
+        assert!(state.iter().any(|(key, _)| key == b"admin_list"));
+        assert!(state
+            .iter()
+            .any(|(key, _)| *key == map_key("allowance", &[spender.address().as_bytes()])));
+    }
+
+    #[test]
+    fn map_keys() {
+        assert_eq!(map_key("config", &[]), b"\x00\x06config".to_vec());
+        assert_eq!(
+            map_key("balances", &[b"inj1abc".as_slice()]),
+            b"\x00\x08balancesinj1abc".to_vec()
+        );
+        assert_eq!(
+            map_key("orders", &[b"inj1abc".as_slice(), &7u64.to_be_bytes()]),
+            [
+                b"\x00\x06orders\x00\x07inj1abc".to_vec(),
+                7u64.to_be_bytes().to_vec()
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn sudo_and_pinned_codes() {
+        let app = InjectiveTestApp::new();
+        let owner = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+        let wasm = Wasm::new(&app);
+
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, &owner)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_address = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![owner.address()],
+                    mutable: false,
+                },
+                None,
+                None,
+                &[],
+                &owner,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        // cw1-whitelist doesn't export a sudo entry point
+        let err = wasm
+            .sudo(
+                &contract_address,
+                &serde_json::json!({ "begin_blocker": {} }),
+            )
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        let pinned_codes = || {
+            wasm.query_pinned_codes(&QueryPinnedCodesRequest { pagination: None })
+                .unwrap()
+                .code_ids
+        };
+
+        wasm.pin_code(&[code_id], &owner).unwrap();
+        assert!(pinned_codes().contains(&code_id));
+
+        wasm.unpin_code(&[code_id], &owner).unwrap();
+        assert!(!pinned_codes().contains(&code_id));
+    }
+
+    #[test]
+    fn contract_errors() {
+        use cw1_whitelist::msg::ExecuteMsg;
+        use cw1_whitelist::ContractError;
+
+        let app = InjectiveTestApp::new();
+        let accs = app
+            .init_accounts(&[Coin::new(100_000_000_000_000_000_000u128, "inj")], 2)
+            .unwrap();
+        let (admin, other) = (&accs[0], &accs[1]);
+        let wasm = Wasm::new(&app);
+
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, admin)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_address = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![admin.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                admin,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let err = wasm
+            .execute::<ExecuteMsg>(&contract_address, &ExecuteMsg::Freeze {}, &[], other)
+            .unwrap_err();
+        assert_eq!(
+            err.contract_error(),
+            Some(crate::ContractError {
+                msg_index: 0,
+                contract_error: ContractError::Unauthorized {}.to_string(),
+                codespace: "wasm".to_string(),
+                code: 5,
+            })
+        );
+
+        crate::assert_contract_err!(
+            wasm.execute::<ExecuteMsg>(&contract_address, &ExecuteMsg::Freeze {}, &[], other),
+            ContractError::Unauthorized {}
+        );
+
+        // errors not coming from a contract
+        assert_eq!(
+            crate::ContractError::parse("insufficient fees; got: 1inj required: 2inj"),
+            None
+        );
+    }
+}
@@ -1,9 +1,35 @@
+use cosmwasm_std::Event;
 use injective_std::types::injective::wasmx::v1;
-
-use test_tube_inj::fn_query;
+use test_tube_inj::account::SigningAccount;
+use test_tube_inj::runner::error::RunnerError;
+use test_tube_inj::runner::result::RunnerResult;
+use test_tube_inj::{fn_execute, fn_query};
 
 use test_tube_inj::runner::Runner;
 
+use crate::{GovWithAppAccess, InjectiveTestApp, GOV_MODULE_ADDRESS};
+
+const CONTRACT_EXECUTION_EVENT: &str = "injective.wasmx.v1.EventContractExecution";
+const BEGIN_BLOCKER_MSG: &str = r#"{"begin_blocker":{}}"#;
+
+/// A `begin_blocker` sudo call made by the wasmx module for a registered contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeginBlockerExecution {
+    pub contract_address: String,
+    /// Estimated gas used by the call. The module doesn't report the gas it charges,
+    /// so this is measured by simulating the call right before the block, without gas
+    /// limit, including the gas used up to the failure of a failing call.
+    pub estimated_gas_used: u64,
+    pub execution_error: String,
+    pub other_error: String,
+}
+
+impl BeginBlockerExecution {
+    pub fn is_ok(&self) -> bool {
+        self.execution_error.is_empty() && self.other_error.is_empty()
+    }
+}
+
 pub struct Wasmx<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -18,7 +44,297 @@ impl<'a, R> Wasmx<'a, R>
 where
     R: Runner<'a>,
 {
+    fn_execute! {
+        pub register_contract: v1::MsgRegisterContract => v1::MsgRegisterContractResponse
+    }
+
+    fn_execute! {
+        pub update_contract: v1::MsgUpdateContract => v1::MsgUpdateContractResponse
+    }
+
+    fn_execute! {
+        pub activate_contract: v1::MsgActivateContract => v1::MsgActivateContractResponse
+    }
+
+    fn_execute! {
+        pub deactivate_contract: v1::MsgDeactivateContract => v1::MsgDeactivateContractResponse
+    }
+
+    fn_execute! {
+        pub execute_contract_compat: v1::MsgExecuteContractCompat => v1::MsgExecuteContractCompatResponse
+    }
+
     fn_query! {
         pub query_contract_registration_info ["/injective.wasmx.v1.Query/ContractRegistrationInfo"]: v1::QueryContractRegistrationInfoRequest => v1::QueryContractRegistrationInfoResponse
     }
+
+    fn_query! {
+        pub query_wasmx_params ["/injective.wasmx.v1.Query/WasmxParams"]: v1::QueryWasmxParamsRequest => v1::QueryWasmxParamsResponse
+    }
+
+    fn_query! {
+        pub query_module_state ["/injective.wasmx.v1.Query/WasmxModuleState"]: v1::QueryModuleStateRequest => v1::QueryModuleStateResponse
+    }
+}
+
+impl<'a> Wasmx<'a, InjectiveTestApp> {
+    /// Register a contract for begin blocker execution through a gov proposal
+    /// submitted by `proposer`, since only the gov module may register contracts.
+    pub fn register_contract_with_gov(
+        &self,
+        request: v1::ContractRegistrationRequest,
+        proposer: &SigningAccount,
+    ) -> RunnerResult<()> {
+        GovWithAppAccess::new(self.runner).propose_and_execute(
+            v1::MsgRegisterContract::TYPE_URL.to_string(),
+            v1::MsgRegisterContract {
+                sender: GOV_MODULE_ADDRESS.to_string(),
+                contract_registration_request: Some(request),
+            },
+            proposer,
+        )?;
+
+        Ok(())
+    }
+
+    /// Finalize the next block, `seconds` after the current one, and return the
+    /// `begin_blocker` sudo calls the wasmx module made for registered contracts.
+    ///
+    /// The calls are read from the block events, their gas is an estimate, see
+    /// `BeginBlockerExecution::estimated_gas_used`.
+    pub fn next_block_begin_blocker_executions(
+        &self,
+        seconds: u64,
+    ) -> RunnerResult<Vec<BeginBlockerExecution>> {
+        let registered_contracts = self
+            .query_module_state(&v1::QueryModuleStateRequest {})?
+            .state
+            .map(|state| state.registered_contracts)
+            .unwrap_or_default();
+
+        let estimated_gas_used = registered_contracts
+            .into_iter()
+            .filter(|c| {
+                c.registered_contract
+                    .as_ref()
+                    .is_some_and(|c| c.is_executable)
+            })
+            .map(|c| {
+                let gas_used = match self.runner.simulate_sudo(&c.address, BEGIN_BLOCKER_MSG) {
+                    Ok(res) => res.gas_used,
+                    Err(RunnerError::ExecuteError { gas_used, .. }) => gas_used,
+                    Err(err) => return Err(err),
+                };
+                Ok((c.address, gas_used))
+            })
+            .collect::<RunnerResult<Vec<_>>>()?;
+
+        self.runner
+            .next_block(seconds)?
+            .into_iter()
+            .filter(|e| e.ty == CONTRACT_EXECUTION_EVENT)
+            .map(|e| {
+                let contract_address = event_attribute(&e, "contract_address");
+                let estimated_gas_used = estimated_gas_used
+                    .iter()
+                    .find(|(address, _)| *address == contract_address)
+                    .map(|(_, gas_used)| *gas_used)
+                    .ok_or_else(|| {
+                        RunnerError::GenericError(format!(
+                            "contract {} was executed without being registered as executable",
+                            contract_address
+                        ))
+                    })?;
+
+                Ok(BeginBlockerExecution {
+                    estimated_gas_used,
+                    execution_error: event_attribute(&e, "execution_error"),
+                    other_error: event_attribute(&e, "other_error"),
+                    contract_address,
+                })
+            })
+            .collect()
+    }
+}
+
+// typed events hold json encoded attribute values, e.g. `"\"inj1...\""`
+fn event_attribute(event: &Event, key: &str) -> String {
+    event
+        .attributes
+        .iter()
+        .find(|a| a.key == key)
+        .map(|a| serde_json::from_str(&a.value).unwrap_or_else(|_| a.value.clone()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+    use cw1_whitelist::msg::InstantiateMsg;
+    use injective_std::types::cosmos::bank::v1beta1::MsgSend;
+    use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+    use injective_std::types::injective::wasmx::v1;
+
+    use crate::{Account, Bank, InjectiveTestApp, Module, Wasm, Wasmx};
+
+    #[test]
+    fn wasmx_register_contract_and_begin_blocker() {
+        let app = InjectiveTestApp::new();
+        let owner = app
+            .init_account(&[Coin::new(100_000_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasmx = Wasmx::new(&app);
+
+        let params = wasmx
+            .query_wasmx_params(&v1::QueryWasmxParamsRequest {})
+            .unwrap()
+            .params
+            .unwrap();
+        assert!(params.is_execution_enabled);
+
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &owner)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_address = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![owner.address()],
+                    mutable: true,
+                },
+                Some(&owner.address()),
+                Some("whitelist"),
+                &[],
+                &owner,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        // the contract pays for its own begin blocker gas
+        Bank::new(&app)
+            .send(
+                MsgSend {
+                    from_address: owner.address(),
+                    to_address: contract_address.clone(),
+                    amount: vec![BaseCoin {
+                        amount: "10000000000000000000".to_string(),
+                        denom: "inj".to_string(),
+                    }],
+                },
+                &owner,
+            )
+            .unwrap();
+
+        wasmx
+            .register_contract_with_gov(
+                v1::ContractRegistrationRequest {
+                    contract_address: contract_address.clone(),
+                    gas_limit: 1_000_000,
+                    gas_price: params.min_gas_price,
+                    should_pin_contract: false,
+                    is_migration_allowed: false,
+                    code_id,
+                    admin_address: owner.address(),
+                    granter_address: "".to_string(),
+                    funding_mode: v1::FundingMode::SelfFunded as i32,
+                },
+                &owner,
+            )
+            .unwrap();
+
+        let registration = wasmx
+            .query_contract_registration_info(&v1::QueryContractRegistrationInfoRequest {
+                contract_address: contract_address.clone(),
+            })
+            .unwrap()
+            .contract
+            .unwrap();
+        assert!(registration.is_executable);
+        assert_eq!(registration.gas_limit, 1_000_000);
+
+        let registered = wasmx
+            .query_module_state(&v1::QueryModuleStateRequest {})
+            .unwrap()
+            .state
+            .unwrap()
+            .registered_contracts;
+        assert!(registered.iter().any(|c| c.address == contract_address));
+
+        // cw1-whitelist has no sudo entry point, so the begin blocker call fails,
+        // after loading the contract
+        let executions = wasmx.next_block_begin_blocker_executions(1).unwrap();
+        let execution = executions
+            .iter()
+            .find(|e| e.contract_address == contract_address)
+            .unwrap();
+        assert!(!execution.is_ok());
+        assert!(execution.estimated_gas_used > 0);
+        assert!(execution.estimated_gas_used < 1_000_000);
+
+        wasmx
+            .update_contract(
+                v1::MsgUpdateContract {
+                    sender: owner.address(),
+                    contract_address: contract_address.clone(),
+                    gas_limit: 2_000_000,
+                    gas_price: params.min_gas_price,
+                    admin_address: owner.address(),
+                },
+                &owner,
+            )
+            .unwrap();
+
+        wasmx
+            .deactivate_contract(
+                v1::MsgDeactivateContract {
+                    sender: owner.address(),
+                    contract_address: contract_address.clone(),
+                },
+                &owner,
+            )
+            .unwrap();
+
+        let registration = wasmx
+            .query_contract_registration_info(&v1::QueryContractRegistrationInfoRequest {
+                contract_address: contract_address.clone(),
+            })
+            .unwrap()
+            .contract
+            .unwrap();
+        assert!(!registration.is_executable);
+        assert_eq!(registration.gas_limit, 2_000_000);
+
+        let executions = wasmx.next_block_begin_blocker_executions(1).unwrap();
+        assert!(executions
+            .iter()
+            .all(|e| e.contract_address != contract_address));
+
+        wasmx
+            .activate_contract(
+                v1::MsgActivateContract {
+                    sender: owner.address(),
+                    contract_address: contract_address.clone(),
+                },
+                &owner,
+            )
+            .unwrap();
+
+        wasmx
+            .execute_contract_compat(
+                v1::MsgExecuteContractCompat {
+                    sender: owner.address(),
+                    contract: contract_address,
+                    msg: r#"{"freeze":{}}"#.to_string(),
+                    funds: "0".to_string(),
+                },
+                &owner,
+            )
+            .unwrap();
+    }
 }
@@ -1,14 +1,22 @@
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, Event, Uint128};
+use injective_std::types::injective::oracle::v1beta1::OracleType;
 use prost::Message;
-use test_tube_inj::account::SigningAccount;
-use test_tube_inj::runner::result::{RunnerExecuteResult, RunnerResult};
+use test_tube_inj::account::{Account, FeeSetting, SigningAccount};
+use test_tube_inj::cosmrs::crypto::secp256k1::SigningKey;
+use test_tube_inj::module::Module;
+use test_tube_inj::runner::app::INJECTIVE_MIN_GAS_PRICE;
+use test_tube_inj::runner::result::{RunnerExecuteResult, RunnerResult, SudoResponse};
 use test_tube_inj::runner::Runner;
 use test_tube_inj::BaseApp;
 
+use crate::module::{Vesting, VestingSchedule};
+
 const FEE_DENOM: &str = "inj";
 const INJ_ADDRESS_PREFIX: &str = "inj";
 const CHAIN_ID: &str = "injective-777";
 const DEFAULT_GAS_ADJUSTMENT: f64 = 1.2;
+// enough to cover the fee of the tx creating a vesting account
+const VESTING_FUNDER_FEE_AMOUNT: u128 = 1_000_000_000_000_000_000;
 
 #[derive(Debug, PartialEq)]
 pub struct InjectiveTestApp {
@@ -84,6 +92,43 @@ impl InjectiveTestApp {
         self.inner.init_accounts(coins, count)
     }
 
+    /// Initialize a vesting account holding `coins` that unlock according to `schedule`.
+    /// The coins are minted to a funding account, which then creates the vesting
+    /// account through the vesting module, just like it would happen on chain.
+    ///
+    /// Note that locked coins can't be used to pay fees, so the account needs
+    /// to receive spendable funds before it can sign transactions.
+    pub fn init_vesting_account(
+        &self,
+        coins: &[Coin],
+        schedule: VestingSchedule,
+    ) -> RunnerResult<SigningAccount> {
+        let mut funder_coins = coins.to_vec();
+        match funder_coins.iter_mut().find(|c| c.denom == FEE_DENOM) {
+            Some(fee_coin) => fee_coin.amount += Uint128::from(VESTING_FUNDER_FEE_AMOUNT),
+            None => funder_coins.push(Coin::new(VESTING_FUNDER_FEE_AMOUNT, FEE_DENOM)),
+        }
+        let funder = self.init_account(&funder_coins)?;
+
+        let account = SigningAccount::new(
+            INJ_ADDRESS_PREFIX.to_string(),
+            SigningKey::random(),
+            FeeSetting::Auto {
+                gas_price: Coin::new(INJECTIVE_MIN_GAS_PRICE, FEE_DENOM),
+                gas_adjustment: DEFAULT_GAS_ADJUSTMENT,
+            },
+        );
+
+        Vesting::new(self).create_vesting_account_with_schedule(
+            &account.address(),
+            coins,
+            &schedule,
+            &funder,
+        )?;
+
+        Ok(account)
+    }
+
     /// Simulate transaction execution and return gas info
     pub fn simulate_tx<I>(
         &self,
@@ -104,6 +149,77 @@ impl InjectiveTestApp {
     ) -> RunnerResult<P> {
         self.inner.get_param_set(subspace, type_url)
     }
+
+    /// Finalize an empty block after increasing the time by `seconds`, returning the
+    /// block level events, e.g. the ones emitted by begin blockers.
+    pub fn next_block(&self, seconds: u64) -> RunnerResult<Vec<Event>> {
+        self.inner.next_block(seconds)
+    }
+
+    /// Call the sudo entry point of `contract` with `msg` through the wasm keeper and
+    /// commit the state changes, returning its response data and the gas it used.
+    pub fn sudo(&self, contract: &str, msg: &str) -> RunnerResult<SudoResponse> {
+        self.inner.sudo(contract, msg)
+    }
+
+    /// Call the sudo entry point of `contract` with `msg` without committing the state
+    /// changes, returning its response data and the gas it used. A failing call returns
+    /// an `ExecuteError` holding the gas used up to the failure.
+    pub fn simulate_sudo(&self, contract: &str, msg: &str) -> RunnerResult<SudoResponse> {
+        self.inner.simulate_sudo(contract, msg)
+    }
+
+    /// Set the price of `base`/`quote` for `oracle_type` directly in the oracle keeper,
+    /// bypassing relayer privileges and price attestations. `price` is a decimal string, e.g. `"12.5"`.
+    ///
+    /// Symbol based oracles (Band, Coinbase, Pyth and Stork) price a pair as the base price over
+    /// the quote price, so the quote symbol is set to 1 unless it already has a price, and the
+    /// base symbol is scaled to it. For the provider oracle `base` is the symbol and `quote` is
+    /// the provider.
+    pub fn set_oracle_price(
+        &self,
+        oracle_type: OracleType,
+        base: &str,
+        quote: &str,
+        price: &str,
+    ) -> RunnerResult<()> {
+        self.inner
+            .set_oracle_price(oracle_type as i32, base, quote, price)
+    }
+
+    /// Script the price path of `base`/`quote` by setting one of `prices` per block,
+    /// advancing the block time by `block_interval` seconds after each of them.
+    pub fn set_oracle_price_path(
+        &self,
+        oracle_type: OracleType,
+        base: &str,
+        quote: &str,
+        prices: &[&str],
+        block_interval: u64,
+    ) -> RunnerResult<()> {
+        for price in prices {
+            self.set_oracle_price(oracle_type, base, quote, price)?;
+            self.increase_time(block_interval);
+        }
+
+        Ok(())
+    }
+
+    /// Write `value` under `key` in the raw storage of `contract`, e.g. to craft a legacy
+    /// storage layout before testing a migration. See [`crate::map_key`] for map keys.
+    pub fn set_contract_storage(
+        &self,
+        contract: &str,
+        key: &[u8],
+        value: &[u8],
+    ) -> RunnerResult<()> {
+        self.inner.set_contract_storage(contract, key, value)
+    }
+
+    /// Remove `key` from the raw storage of `contract`.
+    pub fn delete_contract_storage(&self, contract: &str, key: &[u8]) -> RunnerResult<()> {
+        self.inner.delete_contract_storage(contract, key)
+    }
 }
 
 impl<'a> Runner<'a> for InjectiveTestApp {
@@ -144,6 +260,9 @@ mod tests {
     use cosmwasm_std::{coins, Coin};
     use injective_std::types::{
         cosmos::bank::v1beta1::QueryAllBalancesRequest,
+        injective::oracle::v1beta1::{
+            OracleType, QueryCoinbasePriceStatesRequest, QueryOraclePriceRequest,
+        },
         injective::tokenfactory::v1beta1::{
             MsgCreateDenom, MsgCreateDenomResponse, QueryParamsRequest, QueryParamsResponse,
         },
@@ -151,7 +270,7 @@ mod tests {
 
     use crate::module::Wasm;
     use crate::runner::app::InjectiveTestApp;
-    use crate::Bank;
+    use crate::{Bank, Oracle, RunnerError};
     use test_tube_inj::account::{Account, FeeSetting};
     use test_tube_inj::module::Module;
     use test_tube_inj::runner::*;
@@ -478,4 +597,222 @@ mod tests {
         assert_eq!(res.gas_info.gas_wanted, gas_limit);
         assert_eq!(bob_balance, initial_balance - amount.amount.u128());
     }
+
+    #[test]
+    fn test_set_oracle_price() {
+        let app = InjectiveTestApp::default();
+        let oracle = Oracle::new(&app);
+
+        let pair_price = || {
+            oracle
+                .query_oracle_price(&QueryOraclePriceRequest {
+                    oracle_type: OracleType::PriceFeed as i32,
+                    base: "inj".to_string(),
+                    quote: "usdt".to_string(),
+                    scaling_options: None,
+                })
+                .unwrap()
+                .price_pair_state
+                .unwrap()
+                .pair_price
+        };
+
+        app.set_oracle_price(OracleType::PriceFeed, "inj", "usdt", "12.5")
+            .unwrap();
+        assert_eq!(pair_price(), "12500000000000000000");
+
+        let block_time = app.get_block_time_seconds();
+        app.set_oracle_price_path(OracleType::PriceFeed, "inj", "usdt", &["13", "14"], 10)
+            .unwrap();
+        assert_eq!(pair_price(), "14000000000000000000");
+        assert!(app.get_block_time_seconds() >= block_time + 20);
+
+        let err = app
+            .set_oracle_price(OracleType::PriceFeed, "inj", "usdt", "not a price")
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+    }
+
+    #[test]
+    fn test_set_symbol_oracle_price() {
+        let app = InjectiveTestApp::default();
+        let oracle = Oracle::new(&app);
+
+        let pyth_id = |n: u8| format!("0x{}", hex::encode([n; 32]));
+        let (pyth_inj, pyth_usdt, pyth_usd) = (pyth_id(1), pyth_id(2), pyth_id(3));
+
+        for (oracle_type, base, quote, usd) in [
+            (OracleType::Band, "INJ", "USDT", "USD"),
+            (OracleType::BandIbc, "INJ", "USDT", "USD"),
+            (OracleType::Pyth, &pyth_inj, &pyth_usdt, &pyth_usd),
+            (OracleType::Stork, "INJUSD", "USDTUSD", "USDUSD"),
+        ] {
+            let pair_price = |base: &str, quote: &str| {
+                oracle
+                    .query_oracle_price(&QueryOraclePriceRequest {
+                        oracle_type: oracle_type as i32,
+                        base: base.to_string(),
+                        quote: quote.to_string(),
+                        scaling_options: None,
+                    })
+                    .unwrap()
+                    .price_pair_state
+                    .unwrap()
+                    .pair_price
+            };
+
+            // the quote is priced at 1 while it has no price
+            app.set_oracle_price(oracle_type, base, quote, "12.5")
+                .unwrap();
+            assert_eq!(pair_price(base, quote), "12500000000000000000");
+
+            app.set_oracle_price(oracle_type, quote, usd, "2").unwrap();
+            assert_eq!(pair_price(base, quote), "6250000000000000000");
+
+            // an existing quote price is kept, and the base price scaled to it
+            app.set_oracle_price(oracle_type, base, quote, "12.5")
+                .unwrap();
+            assert_eq!(pair_price(base, quote), "12500000000000000000");
+            assert_eq!(pair_price(quote, usd), "2000000000000000000");
+            assert_eq!(pair_price(base, usd), "25000000000000000000");
+        }
+    }
+
+    #[test]
+    fn test_set_coinbase_oracle_price() {
+        let app = InjectiveTestApp::default();
+        let oracle = Oracle::new(&app);
+
+        let price_state = |key: &str| {
+            oracle
+                .query_coinbase_price_states(&QueryCoinbasePriceStatesRequest {})
+                .unwrap()
+                .coinbase_price_states
+                .into_iter()
+                .find(|state| state.key == key)
+                .unwrap()
+        };
+
+        app.set_oracle_price(OracleType::Coinbase, "INJ", "USDT", "12.5")
+            .unwrap();
+        let state = price_state("INJ");
+        assert_eq!(state.value, 12_500_000);
+        assert_eq!(state.price_state.unwrap().price, "12500000000000000000");
+        assert_eq!(price_state("USDT").value, 1_000_000);
+
+        app.set_oracle_price(OracleType::Coinbase, "USDT", "USD", "2")
+            .unwrap();
+        app.set_oracle_price(OracleType::Coinbase, "INJ", "USDT", "12.5")
+            .unwrap();
+        assert_eq!(price_state("USDT").value, 2_000_000);
+        assert_eq!(price_state("INJ").value, 25_000_000);
+
+        // coinbase prices are relayed as u64 with 6 decimals
+        for price in ["20000000000000", "-1"] {
+            let err = app
+                .set_oracle_price(OracleType::Coinbase, "INJ", "USD", price)
+                .unwrap_err();
+            assert!(matches!(err, RunnerError::ExecuteError { .. }));
+        }
+    }
+
+    #[test]
+    fn test_set_and_delete_contract_storage() {
+        use cw1_whitelist::msg::*;
+
+        let app = InjectiveTestApp::default();
+        let accs = app
+            .init_accounts(&coins(100_000_000_000_000_000_000u128, "inj"), 2)
+            .unwrap();
+        let (admin, other) = (&accs[0], &accs[1]);
+
+        let wasm = Wasm::new(&app);
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, admin)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![admin.address()],
+                    mutable: false,
+                },
+                None,
+                None,
+                &[],
+                admin,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        // overwrite the admin list, which the contract itself can't do once immutable
+        let admin_list = format!(r#"{{"admins":["{}"],"mutable":true}}"#, other.address());
+        app.set_contract_storage(&contract_addr, b"admin_list", admin_list.as_bytes())
+            .unwrap();
+
+        let res = wasm
+            .query::<QueryMsg, AdminListResponse>(&contract_addr, &QueryMsg::AdminList {})
+            .unwrap();
+        assert_eq!(res.admins, vec![other.address()]);
+        assert!(res.mutable);
+
+        app.delete_contract_storage(&contract_addr, b"admin_list")
+            .unwrap();
+        assert!(wasm
+            .query_raw(&contract_addr, b"admin_list")
+            .unwrap()
+            .is_none());
+        wasm.query::<QueryMsg, AdminListResponse>(&contract_addr, &QueryMsg::AdminList {})
+            .unwrap_err();
+
+        let err = app
+            .set_contract_storage(&admin.address(), b"admin_list", b"{}")
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+    }
+
+    #[test]
+    fn test_tx_error_codes() {
+        use injective_std::types::cosmos::bank::v1beta1::MsgSend;
+        use injective_std::types::cosmos::base::v1beta1::Coin as BaseCoin;
+
+        let app = InjectiveTestApp::default();
+        let accs = app
+            .init_accounts(&coins(1_000_000_000_000_000_000u128, "inj"), 2)
+            .unwrap();
+
+        let err = Bank::new(&app)
+            .send(
+                MsgSend {
+                    from_address: accs[0].address(),
+                    to_address: accs[1].address(),
+                    amount: vec![BaseCoin {
+                        amount: "1".to_string(),
+                        denom: "usdt".to_string(),
+                    }],
+                },
+                &accs[0],
+            )
+            .unwrap_err();
+
+        match err {
+            RunnerError::ExecuteError {
+                codespace,
+                code,
+                gas_wanted,
+                gas_used,
+                ..
+            } => {
+                // sdkerrors.ErrInsufficientFunds
+                assert_eq!(codespace, "sdk");
+                assert_eq!(code, 5);
+                assert!(gas_used > 0);
+                assert!(gas_wanted >= gas_used);
+            }
+            err => panic!("expected an execute error, got: {:?}", err),
+        }
+    }
 }
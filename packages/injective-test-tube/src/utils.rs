@@ -0,0 +1,184 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Decimal256, StdError, StdResult, Uint256};
+use injective_cosmwasm::checked_address_to_subaccount_id;
+use test_tube_inj::account::Account;
+
+/// Subaccount id of `account` with the given nonce, i.e. `0x{eth_address}{nonce:024x}`.
+pub fn subaccount_id(account: &impl Account, nonce: u32) -> String {
+    checked_address_to_subaccount_id(&Addr::unchecked(account.address()), nonce)
+        .as_str()
+        .to_string()
+}
+
+/// Default subaccount id of `account`, which is the one with nonce 0.
+pub fn default_subaccount_id(account: &impl Account) -> String {
+    subaccount_id(account, 0)
+}
+
+/// Decimals of a market, used to convert human readable prices and quantities
+/// from and to their chain representation.
+///
+/// Chain values are the strings used in exchange messages and query responses,
+/// which are decimals scaled by 10^18, e.g. `"1000000000000000000"` for 1.
+/// Spot prices are quoted per base unit, so they depend on both decimals, while
+/// derivative quantities aren't scaled, which is why derivative markets have no
+/// base decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketDecimals {
+    pub base: u32,
+    pub quote: u32,
+}
+
+impl MarketDecimals {
+    pub fn spot(base: u32, quote: u32) -> Self {
+        Self { base, quote }
+    }
+
+    pub fn derivative(quote: u32) -> Self {
+        Self { base: 0, quote }
+    }
+
+    /// Chain representation of a human readable price, e.g. `"1.5"`.
+    ///
+    /// Fails if the price overflows or has more decimal places than the chain representation.
+    pub fn price_to_chain(&self, price: &str) -> StdResult<String> {
+        Ok(self
+            .price_to_chain_decimal(Decimal256::from_str(price)?)?
+            .atomics()
+            .to_string())
+    }
+
+    /// Chain representation of a human readable quantity, e.g. `"0.25"`.
+    ///
+    /// Fails if the quantity overflows or has more decimal places than the chain representation.
+    pub fn quantity_to_chain(&self, quantity: &str) -> StdResult<String> {
+        Ok(self
+            .quantity_to_chain_decimal(Decimal256::from_str(quantity)?)?
+            .atomics()
+            .to_string())
+    }
+
+    /// Human readable price of a chain price.
+    ///
+    /// Fails if the price has more decimal places than a human readable decimal, i.e. 18.
+    pub fn price_from_chain(&self, price: &str) -> StdResult<String> {
+        Ok(self.price_from_chain_decimal(price)?.to_string())
+    }
+
+    /// Human readable quantity of a chain quantity.
+    ///
+    /// Fails if the quantity has more decimal places than a human readable decimal, i.e. 18.
+    pub fn quantity_from_chain(&self, quantity: &str) -> StdResult<String> {
+        Ok(self.quantity_from_chain_decimal(quantity)?.to_string())
+    }
+
+    pub(crate) fn price_to_chain_decimal(&self, price: Decimal256) -> StdResult<Decimal256> {
+        scale(price.atomics(), self.quote, self.base).map(Decimal256::new)
+    }
+
+    pub(crate) fn quantity_to_chain_decimal(&self, quantity: Decimal256) -> StdResult<Decimal256> {
+        scale(quantity.atomics(), self.base, 0).map(Decimal256::new)
+    }
+
+    pub(crate) fn price_from_chain_decimal(&self, price: &str) -> StdResult<Decimal256> {
+        scale(Uint256::from_str(price)?, self.base, self.quote).map(Decimal256::new)
+    }
+
+    pub(crate) fn quantity_from_chain_decimal(&self, quantity: &str) -> StdResult<Decimal256> {
+        scale(Uint256::from_str(quantity)?, 0, self.base).map(Decimal256::new)
+    }
+}
+
+// multiply the atomics of a decimal by 10^mul_exp and divide them by 10^div_exp,
+// failing rather than truncating when the division leaves a remainder
+fn scale(atomics: Uint256, mul_exp: u32, div_exp: u32) -> StdResult<Uint256> {
+    let ten = Uint256::from(10u8);
+    let scaled = atomics.checked_mul(ten.checked_pow(mul_exp)?)?;
+    let divisor = ten.checked_pow(div_exp)?;
+
+    if !scaled.checked_rem(divisor)?.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "{} can't be scaled down by 10^{} without losing precision",
+            scaled, div_exp
+        )));
+    }
+
+    Ok(scaled.checked_div(divisor)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Coin;
+
+    use super::{default_subaccount_id, subaccount_id, MarketDecimals};
+    use crate::{Account, InjectiveTestApp};
+
+    #[test]
+    fn subaccount_ids() {
+        let app = InjectiveTestApp::new();
+        let account = app
+            .init_account(&[Coin::new(1_000_000_000_000_000_000u128, "inj")])
+            .unwrap();
+
+        let id = subaccount_id(&account, 1);
+        let eth_address = hex::encode(account.account_id().to_bytes());
+
+        assert_eq!(id, format!("0x{}{:024x}", eth_address, 1));
+        assert_eq!(
+            default_subaccount_id(&account),
+            format!("0x{}{:024x}", eth_address, 0)
+        );
+    }
+
+    #[test]
+    fn market_decimals_conversions() {
+        // INJ/USDT spot market
+        let spot = MarketDecimals::spot(18, 6);
+        assert_eq!(spot.price_to_chain("1.5").unwrap(), "1500000");
+        assert_eq!(spot.price_from_chain("1500000").unwrap(), "1.5");
+        assert_eq!(
+            spot.quantity_to_chain("0.25").unwrap(),
+            "250000000000000000000000000000000000"
+        );
+        assert_eq!(
+            spot.quantity_from_chain("250000000000000000000000000000000000")
+                .unwrap(),
+            "0.25"
+        );
+
+        // INJ/USDT perpetual market
+        let derivative = MarketDecimals::derivative(6);
+        assert_eq!(
+            derivative.price_to_chain("25.1").unwrap(),
+            "25100000000000000000000000"
+        );
+        assert_eq!(
+            derivative
+                .price_from_chain("25100000000000000000000000")
+                .unwrap(),
+            "25.1"
+        );
+        assert_eq!(
+            derivative.quantity_to_chain("0.001").unwrap(),
+            "1000000000000000"
+        );
+        assert_eq!(
+            derivative.quantity_from_chain("1000000000000000").unwrap(),
+            "0.001"
+        );
+
+        assert!(spot.price_to_chain("not a price").is_err());
+
+        // less than one atomic unit on chain
+        assert!(spot.price_to_chain("0.0000001").is_err());
+        assert!(spot.quantity_from_chain("1").is_err());
+        // overflows once scaled by the quote decimals
+        assert!(derivative
+            .price_to_chain(&format!("1{}", "0".repeat(58)))
+            .is_err());
+        assert!(MarketDecimals::spot(u32::MAX, 0)
+            .quantity_to_chain("1")
+            .is_err());
+    }
+}
@@ -220,6 +220,9 @@ extern "C" {
 extern "C" {
     pub fn IncreaseTime(envId: GoUint64, seconds: GoInt64);
 }
+extern "C" {
+    pub fn NextBlock(envId: GoUint64, seconds: GoUint64) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn Query(
         envId: GoUint64,
@@ -250,6 +253,38 @@ extern "C" {
         typeUrl: GoString,
     ) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn SetOraclePrice(
+        envId: GoUint64,
+        oracleType: GoInt32,
+        base: GoString,
+        quote: GoString,
+        price: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SetContractStorage(
+        envId: GoUint64,
+        bech32ContractAddress: GoString,
+        base64Key: GoString,
+        base64Value: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn DeleteContractStorage(
+        envId: GoUint64,
+        bech32ContractAddress: GoString,
+        base64Key: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn Sudo(
+        envId: GoUint64,
+        bech32ContractAddress: GoString,
+        msgJson: GoString,
+        simulate: GoUint8,
+    ) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn GetValidatorAddress(envId: GoUint64, n: GoInt32) -> *mut ::std::os::raw::c_char;
 }
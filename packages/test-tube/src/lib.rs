@@ -12,6 +12,8 @@ pub use cosmrs;
 pub use account::{Account, NonSigningAccount, SigningAccount};
 pub use module::*;
 pub use runner::app::BaseApp;
-pub use runner::error::{DecodeError, EncodeError, RunnerError};
-pub use runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
+pub use runner::error::{ContractError, DecodeError, EncodeError, RunnerError};
+pub use runner::result::{
+    ExecuteMultipleResponse, ExecuteResponse, RunnerExecuteResult, RunnerResult, SudoResponse,
+};
 pub use runner::Runner;
@@ -51,3 +51,28 @@ macro_rules! fn_query {
         }
     };
 }
+
+/// Assert that `$res` failed with the contract error `$err`, comparing its `Display` output
+/// with the error returned by the contract.
+///
+/// ```ignore
+/// assert_contract_err!(
+///     wasm.execute(&contract, &ExecuteMsg::Freeze {}, &[], &not_admin),
+///     ContractError::Unauthorized {}
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_contract_err {
+    ($res:expr, $err:expr) => {{
+        let err = match $res {
+            Ok(_) => panic!(
+                "expected contract error `{}`, but execution succeeded",
+                $err
+            ),
+            Err(err) => err,
+        };
+        let contract_error = $crate::RunnerError::contract_error(&err)
+            .unwrap_or_else(|| panic!("expected contract error `{}`, got: {}", $err, err));
+        assert_eq!(contract_error.contract_error, $err.to_string());
+    }};
+}
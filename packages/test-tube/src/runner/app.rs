@@ -6,19 +6,19 @@ use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::proto::tendermint::v0_38::abci::ResponseFinalizeBlock;
 use cosmrs::tx;
 use cosmrs::tx::{Fee, SignerInfo};
-use cosmwasm_std::{Coin, Timestamp};
+use cosmwasm_std::{Attribute, Coin, Event, Timestamp};
 use prost::Message;
 
 use crate::account::{Account, FeeSetting, SigningAccount};
 use crate::bindings::{
-    AccountNumber, AccountSequence, CleanUp, FinalizeBlock, GetBlockHeight, GetBlockTime,
-    GetParamSet, GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime, InitAccount,
-    InitTestEnv, Query, Simulate,
+    AccountNumber, AccountSequence, CleanUp, DeleteContractStorage, FinalizeBlock, GetBlockHeight,
+    GetBlockTime, GetParamSet, GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime,
+    InitAccount, InitTestEnv, NextBlock, Query, SetContractStorage, SetOraclePrice, Simulate, Sudo,
 };
 use crate::redefine_as_go_string;
 use crate::runner::error::{DecodeError, EncodeError, RunnerError};
 use crate::runner::result::RawResult;
-use crate::runner::result::{RunnerExecuteResult, RunnerResult};
+use crate::runner::result::{RunnerExecuteResult, RunnerResult, SudoResponse};
 use crate::runner::Runner;
 
 pub const INJECTIVE_MIN_GAS_PRICE: u128 = 2_500;
@@ -56,6 +56,30 @@ impl BaseApp {
         }
     }
 
+    /// Finalize an empty block after increasing the time by the given number of seconds,
+    /// and return the events emitted outside of transactions, e.g. by begin and end blockers.
+    pub fn next_block(&self, seconds: u64) -> RunnerResult<Vec<Event>> {
+        let res = unsafe {
+            let res = NextBlock(self.id, seconds);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        let res =
+            ResponseFinalizeBlock::decode(res.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+
+        Ok(res
+            .events
+            .into_iter()
+            .map(|e| {
+                Event::new(e.r#type).add_attributes(
+                    e.attributes
+                        .into_iter()
+                        .map(|a| Attribute::new(a.key, a.value)),
+                )
+            })
+            .collect())
+    }
+
     /// Get the first validator address
     pub fn get_first_validator_address(&self) -> RunnerResult<String> {
         let addr = unsafe {
@@ -310,6 +334,107 @@ impl BaseApp {
             Ok(pset)
         }
     }
+
+    /// Call the sudo entry point of a contract through the wasm keeper, the way modules
+    /// such as wasmx or gov do, and commit the resulting state changes.
+    pub fn sudo(&self, contract: &str, msg: &str) -> RunnerResult<SudoResponse> {
+        let res = self.run_sudo(contract, msg, false)?;
+
+        let empty_tx = "".to_string();
+        redefine_as_go_string!(empty_tx);
+        unsafe {
+            FinalizeBlock(self.id, empty_tx);
+        }
+
+        Ok(res)
+    }
+
+    /// Call the sudo entry point of a contract without committing any state change,
+    /// to find out the gas it uses. A failing call returns an `ExecuteError`
+    /// holding the gas used up to the failure.
+    pub fn simulate_sudo(&self, contract: &str, msg: &str) -> RunnerResult<SudoResponse> {
+        self.run_sudo(contract, msg, true)
+    }
+
+    fn run_sudo(&self, contract: &str, msg: &str, simulate: bool) -> RunnerResult<SudoResponse> {
+        redefine_as_go_string!(contract);
+        redefine_as_go_string!(msg);
+
+        let res = unsafe {
+            let res = Sudo(self.id, contract, msg, simulate.into());
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        serde_json::from_slice(&res).map_err(|e| DecodeError::JsonDecodeError(e).into())
+    }
+
+    /// Set the price of `base`/`quote` for the given oracle type directly in the oracle
+    /// keeper, bypassing relayer privileges. `price` is a decimal string, e.g. `"12.5"`.
+    pub fn set_oracle_price(
+        &self,
+        oracle_type: i32,
+        base: &str,
+        quote: &str,
+        price: &str,
+    ) -> RunnerResult<()> {
+        redefine_as_go_string!(base);
+        redefine_as_go_string!(quote);
+        redefine_as_go_string!(price);
+
+        let empty_tx = "".to_string();
+        redefine_as_go_string!(empty_tx);
+
+        unsafe {
+            let res = SetOraclePrice(self.id, oracle_type, base, quote, price);
+            RawResult::from_non_null_ptr(res).into_result()?;
+            FinalizeBlock(self.id, empty_tx);
+        }
+
+        Ok(())
+    }
+
+    /// Write `value` under `key` in the raw storage of `contract`, bypassing the contract.
+    pub fn set_contract_storage(
+        &self,
+        contract: &str,
+        key: &[u8],
+        value: &[u8],
+    ) -> RunnerResult<()> {
+        let base64_key = BASE64_STANDARD.encode(key);
+        let base64_value = BASE64_STANDARD.encode(value);
+        redefine_as_go_string!(contract);
+        redefine_as_go_string!(base64_key);
+        redefine_as_go_string!(base64_value);
+
+        let empty_tx = "".to_string();
+        redefine_as_go_string!(empty_tx);
+
+        unsafe {
+            let res = SetContractStorage(self.id, contract, base64_key, base64_value);
+            RawResult::from_non_null_ptr(res).into_result()?;
+            FinalizeBlock(self.id, empty_tx);
+        }
+
+        Ok(())
+    }
+
+    /// Remove `key` from the raw storage of `contract`, bypassing the contract.
+    pub fn delete_contract_storage(&self, contract: &str, key: &[u8]) -> RunnerResult<()> {
+        let base64_key = BASE64_STANDARD.encode(key);
+        redefine_as_go_string!(contract);
+        redefine_as_go_string!(base64_key);
+
+        let empty_tx = "".to_string();
+        redefine_as_go_string!(empty_tx);
+
+        unsafe {
+            let res = DeleteContractStorage(self.id, contract, base64_key);
+            RawResult::from_non_null_ptr(res).into_result()?;
+            FinalizeBlock(self.id, empty_tx);
+        }
+
+        Ok(())
+    }
 }
 
 /// Cleanup the test environment when the app is dropped.
@@ -1,6 +1,7 @@
 use cosmrs::rpc::error::Error as TendermintRpcError;
 use cosmrs::tendermint::Error as TendermintError;
 use cosmrs::ErrorReport;
+use cosmwasm_std::Event;
 use std::str::Utf8Error;
 use thiserror::Error;
 
@@ -15,8 +16,19 @@ pub enum RunnerError {
     #[error("query error: {}", .msg)]
     QueryError { msg: String },
 
+    /// Failed transaction, sudo call or cheatcode, with the SDK error it failed with, e.g.
+    /// codespace `sdk` and code 5 for insufficient funds. Failures that don't come from an
+    /// SDK error have an empty codespace and code 0, and only transactions that reached a
+    /// block have events.
     #[error("execute error: {}", .msg)]
-    ExecuteError { msg: String },
+    ExecuteError {
+        msg: String,
+        codespace: String,
+        code: u32,
+        gas_wanted: u64,
+        gas_used: u64,
+        events: Vec<Event>,
+    },
 
     #[error("{0}")]
     GenericError(String),
@@ -37,7 +49,31 @@ impl PartialEq for RunnerError {
             (RunnerError::EncodeError(a), RunnerError::EncodeError(b)) => a == b,
             (RunnerError::DecodeError(a), RunnerError::DecodeError(b)) => a == b,
             (RunnerError::QueryError { msg: a }, RunnerError::QueryError { msg: b }) => a == b,
-            (RunnerError::ExecuteError { msg: a }, RunnerError::ExecuteError { msg: b }) => a == b,
+            (
+                RunnerError::ExecuteError {
+                    msg: a_msg,
+                    codespace: a_codespace,
+                    code: a_code,
+                    gas_wanted: a_gas_wanted,
+                    gas_used: a_gas_used,
+                    events: a_events,
+                },
+                RunnerError::ExecuteError {
+                    msg: b_msg,
+                    codespace: b_codespace,
+                    code: b_code,
+                    gas_wanted: b_gas_wanted,
+                    gas_used: b_gas_used,
+                    events: b_events,
+                },
+            ) => {
+                a_msg == b_msg
+                    && a_codespace == b_codespace
+                    && a_code == b_code
+                    && a_gas_wanted == b_gas_wanted
+                    && a_gas_used == b_gas_used
+                    && a_events == b_events
+            }
             (RunnerError::ErrorReport(a), RunnerError::ErrorReport(b)) => {
                 a.to_string() == b.to_string()
             }
@@ -50,6 +86,83 @@ impl PartialEq for RunnerError {
     }
 }
 
+impl RunnerError {
+    /// `ExecuteError` of a failure that doesn't come from an SDK error.
+    pub fn execute_error(msg: impl Into<String>) -> Self {
+        RunnerError::ExecuteError {
+            msg: msg.into(),
+            codespace: String::new(),
+            code: 0,
+            gas_wanted: 0,
+            gas_used: 0,
+            events: vec![],
+        }
+    }
+
+    /// Error returned by a contract, if this is the execute error of a failed wasm message.
+    pub fn contract_error(&self) -> Option<ContractError> {
+        match self {
+            RunnerError::ExecuteError {
+                msg,
+                codespace,
+                code,
+                ..
+            } => ContractError::parse(msg).map(|err| {
+                if codespace.is_empty() {
+                    err
+                } else {
+                    ContractError {
+                        codespace: codespace.clone(),
+                        code: *code,
+                        ..err
+                    }
+                }
+            }),
+            _ => None,
+        }
+    }
+}
+
+const MESSAGE_INDEX_PREFIX: &str = "failed to execute message; message index: ";
+
+// wasmd errors wrapping contract errors, see `x/wasm/types/errors.go`
+const WASM_CODESPACE: &str = "wasm";
+const WASM_ERRORS: [(&str, u32); 3] = [
+    ("instantiate wasm contract failed", 4),
+    ("execute wasm contract failed", 5),
+    ("migrate wasm contract failed", 11),
+];
+
+/// Error returned by a contract, parsed from the log of the failed transaction, e.g.
+/// `failed to execute message; message index: 0: Unauthorized: execute wasm contract failed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractError {
+    /// Index of the failed message in the transaction.
+    pub msg_index: u64,
+    /// `Display` output of the error returned by the contract.
+    pub contract_error: String,
+    pub codespace: String,
+    pub code: u32,
+}
+
+impl ContractError {
+    /// Parse the log of a failed transaction, `None` if it isn't a contract error.
+    pub fn parse(log: &str) -> Option<Self> {
+        let (msg_index, error) = log.strip_prefix(MESSAGE_INDEX_PREFIX)?.split_once(": ")?;
+        let msg_index = msg_index.parse().ok()?;
+
+        WASM_ERRORS.iter().find_map(|(description, code)| {
+            let contract_error = error.strip_suffix(description)?.strip_suffix(": ")?;
+            Some(ContractError {
+                msg_index,
+                contract_error: contract_error.to_string(),
+                codespace: WASM_CODESPACE.to_string(),
+                code: *code,
+            })
+        })
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum DecodeError {
     #[error("invalid utf8 bytes")]
@@ -1,7 +1,7 @@
 use cosmwasm_std::CosmosMsg;
 
 use crate::account::SigningAccount;
-use crate::runner::result::{RunnerExecuteResult, RunnerResult};
+use crate::runner::result::{ExecuteMultipleResponse, RunnerExecuteResult, RunnerResult};
 use crate::utils::{bank_msg_to_any, wasm_msg_to_any};
 use crate::RunnerError;
 
@@ -40,6 +40,17 @@ pub trait Runner<'a> {
     where
         R: ::prost::Message + Default;
 
+    /// Execute `msgs` in a single transaction and return the responses of all of them,
+    /// which may be of different types, see [`ExecuteMultipleResponse::decode_msg_response`].
+    fn execute_multiple_typed(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+    ) -> RunnerResult<ExecuteMultipleResponse> {
+        // decoding into `()` ignores the first msg response, all of them are read from raw data
+        self.execute_multiple_raw::<()>(msgs, signer)?.try_into()
+    }
+
     fn execute_cosmos_msgs<S>(
         &self,
         msgs: &[CosmosMsg],
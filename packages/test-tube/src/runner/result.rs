@@ -24,25 +24,90 @@ where
     pub gas_info: GasInfo,
 }
 
-impl<R> TryFrom<ExecTxResult> for ExecuteResponse<R>
+/// Response of a transaction holding the responses of all of its messages, in message order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecuteMultipleResponse {
+    pub msg_responses: Vec<cosmrs::Any>,
+    pub raw_data: Vec<u8>,
+    pub events: Vec<Event>,
+    pub gas_info: GasInfo,
+}
+
+impl ExecuteMultipleResponse {
+    /// Decode the response of the message at `index` into `R`.
+    pub fn decode_msg_response<R>(&self, index: usize) -> RunnerResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        let msg_response = self.msg_responses.get(index).ok_or_else(|| {
+            RunnerError::GenericError(format!(
+                "no msg response at index {}, tx has {} msg responses",
+                index,
+                self.msg_responses.len()
+            ))
+        })?;
+
+        R::decode(msg_response.value.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+
+    /// Type urls of the msg responses, e.g. `/cosmos.bank.v1beta1.MsgSendResponse`.
+    pub fn msg_response_type_urls(&self) -> Vec<&str> {
+        self.msg_responses
+            .iter()
+            .map(|res| res.type_url.as_str())
+            .collect()
+    }
+}
+
+impl<R> TryFrom<ExecuteResponse<R>> for ExecuteMultipleResponse
 where
     R: prost::Message + Default,
 {
     type Error = RunnerError;
 
-    fn try_from(res: ExecTxResult) -> Result<Self, Self::Error> {
+    fn try_from(res: ExecuteResponse<R>) -> Result<Self, Self::Error> {
         let tx_msg_data =
-            TxMsgData::decode(res.data.as_ref()).map_err(DecodeError::ProtoDecodeError)?;
+            TxMsgData::decode(res.raw_data.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
 
-        let msg_data = tx_msg_data
-            .msg_responses
-            // since this tx contains exactly 1 msg
-            // when getting none of them, that means error
-            .first()
-            .ok_or(RunnerError::ExecuteError { msg: res.log })?;
+        Ok(Self {
+            msg_responses: tx_msg_data.msg_responses,
+            raw_data: res.raw_data,
+            events: res.events,
+            gas_info: res.gas_info,
+        })
+    }
+}
 
-        let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+/// Result of a sudo call made directly through the wasm keeper.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct SudoResponse {
+    #[serde(with = "base64_bytes")]
+    pub data: Vec<u8>,
+    pub gas_used: u64,
+}
 
+mod base64_bytes {
+    use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer};
+
+    // go encodes nil byte slices as null
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| BASE64_STANDARD.decode(s).map_err(serde::de::Error::custom))
+            .unwrap_or_else(|| Ok(vec![]))
+    }
+}
+
+impl<R> TryFrom<ExecTxResult> for ExecuteResponse<R>
+where
+    R: prost::Message + Default,
+{
+    type Error = RunnerError;
+
+    fn try_from(res: ExecTxResult) -> Result<Self, Self::Error> {
         let events = res
             .events
             .into_iter()
@@ -61,6 +126,29 @@ where
             })
             .collect::<Result<Vec<Event>, DecodeError>>()?;
 
+        if res.code.is_err() {
+            return Err(RunnerError::ExecuteError {
+                msg: res.log,
+                codespace: res.codespace,
+                code: res.code.value(),
+                gas_wanted: res.gas_wanted as u64,
+                gas_used: res.gas_used as u64,
+                events,
+            });
+        }
+
+        let tx_msg_data =
+            TxMsgData::decode(res.data.as_ref()).map_err(DecodeError::ProtoDecodeError)?;
+
+        let msg_data = tx_msg_data
+            .msg_responses
+            // since this tx contains exactly 1 msg
+            // when getting none of them, that means error
+            .first()
+            .ok_or_else(|| RunnerError::execute_error(res.log))?;
+
+        let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+
         Ok(ExecuteResponse {
             data,
             raw_data: res.data.to_vec(),
@@ -81,18 +169,6 @@ where
 
     fn try_from(tx_commit_response: TxCommitResponse) -> Result<Self, Self::Error> {
         let res = tx_commit_response.tx_result;
-        let tx_msg_data =
-            TxMsgData::decode(res.data.as_ref()).map_err(DecodeError::ProtoDecodeError)?;
-
-        let msg_data = tx_msg_data
-            .msg_responses
-            // since this tx contains exactly 1 msg
-            // when getting none of them, that means error
-            .first()
-            .ok_or(RunnerError::ExecuteError { msg: res.log })?;
-
-        let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
-
         let events = res
             .events
             .into_iter()
@@ -111,6 +187,29 @@ where
             })
             .collect::<Result<Vec<Event>, DecodeError>>()?;
 
+        if res.code.is_err() {
+            return Err(RunnerError::ExecuteError {
+                msg: res.log,
+                codespace: res.codespace,
+                code: res.code.value(),
+                gas_wanted: res.gas_wanted as u64,
+                gas_used: res.gas_used as u64,
+                events,
+            });
+        }
+
+        let tx_msg_data =
+            TxMsgData::decode(res.data.as_ref()).map_err(DecodeError::ProtoDecodeError)?;
+
+        let msg_data = tx_msg_data
+            .msg_responses
+            // since this tx contains exactly 1 msg
+            // when getting none of them, that means error
+            .first()
+            .ok_or_else(|| RunnerError::execute_error(res.log))?;
+
+        let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+
         Ok(Self {
             data,
             raw_data: res.data.to_vec(),
@@ -135,25 +234,7 @@ where
             .tx_results
             .first()
             .or_else(|| res.tx_results.get(1))
-            .ok_or(RunnerError::ExecuteError {
-                msg: "No tx results".to_string(),
-            })?;
-
-        let tx_msg_data =
-            TxMsgData::decode(tx.data.as_ref()).map_err(DecodeError::ProtoDecodeError)?;
-
-        let msg_data = tx_msg_data
-            .msg_responses
-            // NOTE: we ignore the first as it seems to be
-            // the gas spend transaction as mentioned above
-            // this needs some thought for supporting more than
-            // one transaction per block
-            .first()
-            .ok_or(RunnerError::ExecuteError {
-                msg: tx.log.clone(),
-            })?;
-
-        let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+            .ok_or_else(|| RunnerError::execute_error("No tx results"))?;
 
         let events = tx
             .events
@@ -174,6 +255,31 @@ where
             })
             .collect::<Result<Vec<Event>, DecodeError>>()?;
 
+        if tx.code != 0 {
+            return Err(RunnerError::ExecuteError {
+                msg: tx.log.clone(),
+                codespace: tx.codespace.clone(),
+                code: tx.code,
+                gas_wanted: tx.gas_wanted as u64,
+                gas_used: tx.gas_used as u64,
+                events,
+            });
+        }
+
+        let tx_msg_data =
+            TxMsgData::decode(tx.data.as_ref()).map_err(DecodeError::ProtoDecodeError)?;
+
+        let msg_data = tx_msg_data
+            .msg_responses
+            // NOTE: we ignore the first as it seems to be
+            // the gas spend transaction as mentioned above
+            // this needs some thought for supporting more than
+            // one transaction per block
+            .first()
+            .ok_or_else(|| RunnerError::execute_error(tx.log.clone()))?;
+
+        let data = R::decode(msg_data.value.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
+
         Ok(Self {
             data,
             raw_data: tx.data.to_vec(),
@@ -186,6 +292,16 @@ where
     }
 }
 
+// error info of a failure that didn't reach a block, e.g. a simulated tx
+#[derive(serde::Deserialize)]
+struct TxErrorInfo {
+    log: String,
+    codespace: String,
+    code: u32,
+    gas_wanted: u64,
+    gas_used: u64,
+}
+
 /// `RawResult` facilitates type conversions between Go and Rust,
 ///
 /// Since Go struct could not be exposed via cgo due to limitations on
@@ -204,6 +320,7 @@ where
 ///   0 -> Ok
 ///   1 -> QueryError
 ///   2 -> ExecuteError
+///   3 -> ExecuteError, with the json encoded ABCI error info of the failure
 ///
 /// The rest are undefined and remaining spaces are reserved for future use.
 #[derive(Debug)]
@@ -236,10 +353,22 @@ impl RawResult {
                 1 => RunnerError::QueryError {
                     msg: content_string,
                 },
-                2 => RunnerError::ExecuteError {
-                    msg: content_string,
+                2 => RunnerError::execute_error(content_string),
+                3 => match serde_json::from_slice::<TxErrorInfo>(content) {
+                    Ok(info) => RunnerError::ExecuteError {
+                        msg: info.log,
+                        codespace: info.codespace,
+                        code: info.code,
+                        gas_wanted: info.gas_wanted,
+                        gas_used: info.gas_used,
+                        events: vec![],
+                    },
+                    Err(e) => RunnerError::DecodeError(DecodeError::JsonDecodeError(e)),
                 },
-                _ => panic!("undefined code: {}", code),
+                _ => RunnerError::GenericError(format!(
+                    "undefined result code {}: {}",
+                    code, content_string
+                )),
             };
             Some(Self(Err(error)))
         }
@@ -1,8 +1,8 @@
 use cosmrs::proto::{
     cosmos::bank::v1beta1::MsgSend,
     cosmwasm::wasm::v1::{
-        MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract,
-        MsgUpdateAdmin,
+        MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgInstantiateContract2,
+        MsgMigrateContract, MsgUpdateAdmin,
     },
 };
 use cosmwasm_std::{BankMsg, Coin, WasmMsg};
@@ -93,6 +93,26 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
                 funds: coins_to_proto(funds),
             },
         ),
+        WasmMsg::Instantiate2 {
+            admin,
+            code_id,
+            label,
+            msg,
+            funds,
+            salt,
+        } => msg_to_any(
+            "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+            &MsgInstantiateContract2 {
+                sender: signer.address(),
+                admin: admin.clone().unwrap_or_default(),
+                code_id: *code_id,
+                label: label.clone(),
+                msg: msg.to_vec(),
+                funds: coins_to_proto(funds),
+                salt: salt.to_vec(),
+                fix_msg: false,
+            },
+        ),
         WasmMsg::Migrate {
             contract_addr,
             new_code_id,
@@ -124,8 +144,6 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
                 sender: signer.address(),
             },
         ),
-        _ => Err(RunnerError::ExecuteError {
-            msg: "Unsupported WasmMsg".to_string(),
-        }),
+        _ => Err(RunnerError::execute_error("Unsupported WasmMsg")),
     }
 }